@@ -2,17 +2,72 @@
 //! Run it with the following command:
 //! cargo run --example json -- examples/sample.json
 
-mod parser;
-
-use std::{env, fs};
-
+use chumsky::error::EmptyErr;
 use chumsky::Parser;
+use chumsky_app::parser;
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            chumsky_app::Chumsky::new(args.max_depth).parse_spanned(src)
+        });
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            chumsky_app::Chumsky::new(args.max_depth).parse_borrowed(src)
+        });
+    }
 
-    let (json, errs) = parser::parser().parse(&src).into_output_errors();
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            chumsky_app::Chumsky::new(args.max_depth).parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            chumsky_app::Chumsky::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    if let Err(err) = json_model::check_depth(&src, args.max_depth) {
+        match args.errors {
+            ErrorFormat::Text => eprintln!("{err}"),
+            ErrorFormat::Json => cli::print_report(&src, &err),
+        }
+        Exit::Parse.exit();
+    }
+    let (json, errs) = parser::parser::<EmptyErr>()
+        .parse(&src)
+        .into_output_errors();
     #[cfg(debug_assertions)]
     {
         println!("{:#?}", json);
@@ -21,7 +76,18 @@ fn main() {
     {
         std::hint::black_box(json);
     }
-    for err in errs {
-        eprintln!("{err}");
+    if !errs.is_empty() {
+        match args.errors {
+            ErrorFormat::Text => {
+                for err in errs {
+                    eprintln!("{err}");
+                }
+            }
+            ErrorFormat::Json => cli::print_report(&src, &chumsky_app::value_error(&src)),
+        }
+        Exit::Parse.exit();
     }
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
@@ -0,0 +1,78 @@
+//! [`parser`](crate::parser) borrowing strings from the input
+
+use chumsky::prelude::*;
+use json_model::escape::unescape;
+use json_model::BorrowedValue;
+
+use crate::parser::Error;
+
+pub fn parser<'a, E: Error<'a>>() -> impl Parser<'a, &'a str, BorrowedValue<'a>, extra::Err<E>> {
+    recursive(|value| {
+        let digits = text::digits(10).to_slice();
+
+        let frac = just('.').then(digits);
+
+        let exp = just('e')
+            .or(just('E'))
+            .then(one_of("+-").or_not())
+            .then(digits);
+
+        let number = just('-')
+            .or_not()
+            .then(text::int(10))
+            .then(frac.or_not())
+            .then(exp.or_not())
+            .to_slice()
+            .map(|s: &str| s.parse().unwrap());
+
+        let escape = just('\\')
+            .then(choice((
+                just('\\'),
+                just('/'),
+                just('"'),
+                just('b').to('\x08'),
+                just('f').to('\x0C'),
+                just('n').to('\n'),
+                just('r').to('\r'),
+                just('t').to('\t'),
+                just('u').then_ignore(text::digits(16).exactly(4)),
+            )))
+            .ignored();
+
+        let string = none_of("\\\"")
+            .ignored()
+            .or(escape)
+            .repeated()
+            .to_slice()
+            // Decoding pairs up surrogates and rejects lone ones, as `unescape` does for every parser
+            .try_map(|s, span| unescape(s).map_err(|_| E::custom(span, "lone surrogate")))
+            .delimited_by(just('"'), just('"'));
+
+        let array = value
+            .clone()
+            .separated_by(just(',').padded())
+            .allow_trailing()
+            .collect()
+            .padded()
+            .delimited_by(just('['), just(']'));
+
+        let member = string.then_ignore(just(':').padded()).then(value);
+        let object = member
+            .clone()
+            .separated_by(just(',').padded())
+            .collect()
+            .padded()
+            .delimited_by(just('{'), just('}'));
+
+        choice((
+            just("null").to(BorrowedValue::Null),
+            just("true").to(BorrowedValue::Bool(true)),
+            just("false").to(BorrowedValue::Bool(false)),
+            number.map(BorrowedValue::Number),
+            string.map(BorrowedValue::String),
+            array.map(BorrowedValue::Array),
+            object.map(BorrowedValue::Object),
+        ))
+        .padded()
+    })
+}
@@ -0,0 +1,123 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use chumsky::error::{EmptyErr, Rich};
+use chumsky::Parser as _;
+use json_model::{check_depth, JsonParser, ParseError, Value, MAX_DEPTH};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Chumsky {
+    pub max_depth: usize,
+}
+
+impl Chumsky {
+    pub const fn new(max_depth: usize) -> Self {
+        Chumsky { max_depth }
+    }
+}
+
+impl Default for Chumsky {
+    fn default() -> Self {
+        Chumsky::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Chumsky {
+    fn name(&self) -> &'static str {
+        "chumsky"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        check_depth(src, self.max_depth)?;
+        parser::parser::<EmptyErr>()
+            .parse(src)
+            .into_result()
+            .map_err(|_| value_error(src))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Chumsky {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        check_depth(src, self.max_depth)?;
+        spanned::parser::<EmptyErr>()
+            .parse(src)
+            .into_result()
+            .map_err(|_| parse_error(&spanned::parser().parse(src).into_errors()))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Chumsky {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        check_depth(src, self.max_depth)?;
+        borrowed::parser::<EmptyErr>()
+            .parse(src)
+            .into_result()
+            .map_err(|_| parse_error(&borrowed::parser().parse(src).into_errors()))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Chumsky {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        check_depth(src, self.max_depth)?;
+        raw_numbers::parser::<EmptyErr>()
+            .parse(src)
+            .into_result()
+            .map_err(|_| parse_error(&raw_numbers::parser().parse(src).into_errors()))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Chumsky {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        check_depth(src, self.max_depth)?;
+        ordered::parser::<EmptyErr>(keys)
+            .parse(src)
+            .into_result()
+            .map_err(|_| parse_error(&ordered::parser(keys).parse(src).into_errors()))
+    }
+}
+
+/// Why [`parser::parser`] rejects `src`, from a parse with [`Rich`] errors
+///
+/// Kept out of the app so that instantiating the parser a second time doesn't change how its
+/// [`EmptyErr`] one is compiled.
+pub fn value_error(src: &str) -> ParseError {
+    parse_error(&parser::parser().parse(src).into_errors())
+}
+
+/// The first of `errs`, with its location and expected set
+///
+/// The parsers are run with [`EmptyErr`] for speed, so only once they have failed are they run
+/// again with [`Rich`] errors for this.
+pub fn parse_error(errs: &[Rich<'_, char>]) -> ParseError {
+    let Some(err) = errs.first() else {
+        return ParseError::new("invalid JSON");
+    };
+    ParseError::new(err.to_string())
+        .with_offset(err.span().start)
+        .with_expected(err.expected().map(ToString::to_string))
+}
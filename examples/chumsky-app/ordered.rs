@@ -0,0 +1,91 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use std::borrow::Cow;
+
+use chumsky::prelude::*;
+use json_model::escape::unescape;
+use json_model::{DuplicateKeys, OrderedValue};
+
+use crate::parser::Error;
+
+/// Repeated keys in an object are handled as `keys` says
+pub fn parser<'a, E: Error<'a>>(
+    keys: DuplicateKeys,
+) -> impl Parser<'a, &'a str, OrderedValue, extra::Err<E>> {
+    recursive(|value| {
+        let digits = text::digits(10).to_slice();
+
+        let frac = just('.').then(digits);
+
+        let exp = just('e')
+            .or(just('E'))
+            .then(one_of("+-").or_not())
+            .then(digits);
+
+        let number = just('-')
+            .or_not()
+            .then(text::int(10))
+            .then(frac.or_not())
+            .then(exp.or_not())
+            .to_slice()
+            .map(|s: &str| s.parse().unwrap());
+
+        let escape = just('\\')
+            .then(choice((
+                just('\\'),
+                just('/'),
+                just('"'),
+                just('b').to('\x08'),
+                just('f').to('\x0C'),
+                just('n').to('\n'),
+                just('r').to('\r'),
+                just('t').to('\t'),
+                just('u').then_ignore(text::digits(16).exactly(4)),
+            )))
+            .ignored();
+
+        let string = none_of("\\\"")
+            .ignored()
+            .or(escape)
+            .repeated()
+            .to_slice()
+            // Decoding pairs up surrogates and rejects lone ones, as `unescape` does for every parser
+            .try_map(|s, span| {
+                unescape(s)
+                    .map(Cow::into_owned)
+                    .map_err(|_| E::custom(span, "lone surrogate"))
+            })
+            .delimited_by(just('"'), just('"'));
+
+        let array = value
+            .clone()
+            .separated_by(just(',').padded())
+            .allow_trailing()
+            .collect()
+            .padded()
+            .delimited_by(just('['), just(']'));
+
+        let member = string.then_ignore(just(':').padded()).then(value);
+        let object = member
+            .clone()
+            .separated_by(just(',').padded())
+            .collect()
+            .padded()
+            .delimited_by(just('{'), just('}'))
+            .try_map(move |members, span| match keys.check(&members) {
+                Ok(()) => Ok(members),
+                Err(_) => Err(E::custom(span, "duplicate key")),
+            });
+
+        choice((
+            just("null").to(OrderedValue::Null),
+            just("true").to(OrderedValue::Bool(true)),
+            just("false").to(OrderedValue::Bool(false)),
+            number.map(OrderedValue::Number),
+            string.map(OrderedValue::String),
+            array.map(OrderedValue::Array),
+            object.map(OrderedValue::Object),
+        ))
+        .padded()
+    })
+}
@@ -0,0 +1,92 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value, from
+//! [`Parser::map_with`]
+
+use std::borrow::Cow;
+
+use chumsky::prelude::*;
+use json_model::escape::unescape;
+use json_model::{Spanned, SpannedValue};
+
+use crate::parser::Error;
+
+pub fn parser<'a, E: Error<'a>>() -> impl Parser<'a, &'a str, Spanned<SpannedValue>, extra::Err<E>>
+{
+    recursive(|value| {
+        let digits = text::digits(10).to_slice();
+
+        let frac = just('.').then(digits);
+
+        let exp = just('e')
+            .or(just('E'))
+            .then(one_of("+-").or_not())
+            .then(digits);
+
+        let number = just('-')
+            .or_not()
+            .then(text::int(10))
+            .then(frac.or_not())
+            .then(exp.or_not())
+            .to_slice()
+            .map(|s: &str| s.parse().unwrap());
+
+        let escape = just('\\')
+            .then(choice((
+                just('\\'),
+                just('/'),
+                just('"'),
+                just('b').to('\x08'),
+                just('f').to('\x0C'),
+                just('n').to('\n'),
+                just('r').to('\r'),
+                just('t').to('\t'),
+                just('u').then_ignore(text::digits(16).exactly(4)),
+            )))
+            .ignored();
+
+        let string = none_of("\\\"")
+            .ignored()
+            .or(escape)
+            .repeated()
+            .to_slice()
+            // Decoding pairs up surrogates and rejects lone ones, as `unescape` does for every parser
+            .try_map(|s, span| {
+                unescape(s)
+                    .map(Cow::into_owned)
+                    .map_err(|_| E::custom(span, "lone surrogate"))
+            })
+            .delimited_by(just('"'), just('"'));
+
+        let array = value
+            .clone()
+            .separated_by(just(',').padded())
+            .allow_trailing()
+            .collect()
+            .padded()
+            .delimited_by(just('['), just(']'));
+
+        let key = string.map_with(|key, e| spanned(key, e.span()));
+        let member = key.then_ignore(just(':').padded()).then(value);
+        let object = member
+            .clone()
+            .separated_by(just(',').padded())
+            .collect()
+            .padded()
+            .delimited_by(just('{'), just('}'));
+
+        choice((
+            just("null").to(SpannedValue::Null),
+            just("true").to(SpannedValue::Bool(true)),
+            just("false").to(SpannedValue::Bool(false)),
+            number.map(SpannedValue::Number),
+            string.map(SpannedValue::String),
+            array.map(SpannedValue::Array),
+            object.map(SpannedValue::Object),
+        ))
+        .map_with(|value, e| spanned(value, e.span()))
+        .padded()
+    })
+}
+
+fn spanned<T>(value: T, span: SimpleSpan) -> Spanned<T> {
+    Spanned::new(value, span.into_range())
+}
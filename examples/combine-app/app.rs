@@ -1,17 +1,49 @@
-#[macro_use]
-extern crate combine;
+use combine::Parser;
+use combine_app::parser;
+use json_model::cli::{self, Args, ErrorFormat, Exit};
 
-mod parser;
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
-use std::{env, fs};
+fn main() {
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
 
-use combine::Parser;
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            combine_app::Combine::new(args.max_depth).parse_spanned(src)
+        });
+    }
 
-fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            combine_app::Combine::new(args.max_depth).parse_borrowed(src)
+        });
+    }
 
-    let mut parser = parser::json_value();
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            combine_app::Combine::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    let mut parser = parser::json_value(args.max_depth);
     match parser.easy_parse(src.as_bytes()) {
         Ok(json) => {
             #[cfg(debug_assertions)]
@@ -24,8 +56,14 @@ fn main() {
             }
         }
         Err(err) => {
-            eprintln!("{:#?}", err);
-            std::process::exit(1);
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{:#?}", err),
+                ErrorFormat::Json => cli::print_report(&src, &combine_app::parse_error(&src, err)),
+            }
+            Exit::Parse.exit();
         }
     };
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
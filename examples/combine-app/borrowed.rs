@@ -0,0 +1,89 @@
+//! [`parser`](crate::parser) borrowing strings from the input
+
+use std::borrow::Cow;
+
+use combine::error::{ParseError, StreamError};
+use combine::stream::StreamErrorFor;
+use combine::{Parser, RangeStream};
+
+use combine::parser::byte::{byte, spaces};
+use combine::parser::choice::choice;
+use combine::parser::item::eof;
+use combine::parser::range;
+use combine::parser::repeat::sep_by;
+use combine::parser::sequence::between;
+
+use json_model::escape::unescape;
+use json_model::{BorrowedMap, BorrowedValue};
+
+use crate::parser::{lex, number_literal, open, string_contents};
+
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
+#[inline(always)]
+pub fn json_value<'a, I>(
+    max_depth: usize,
+) -> impl Parser<Input = I, Output = BorrowedValue<'a>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    spaces().with(json_value_(max_depth)).skip(eof())
+}
+
+parser! {
+    #[inline(always)]
+    fn json_value_['a, I](depth: usize)(I) -> BorrowedValue<'a>
+        where [ I: RangeStream<Item = u8, Range = &'a [u8]> + 'a ]
+    {
+        choice((
+            json_string().map(BorrowedValue::String),
+            object(*depth).map(BorrowedValue::Object),
+            array(*depth).map(BorrowedValue::Array),
+            lex(number_literal()).map(BorrowedValue::Number),
+            lex(range::range(&b"false"[..]).map(|_| BorrowedValue::Bool(false))),
+            lex(range::range(&b"true"[..]).map(|_| BorrowedValue::Bool(true))),
+            lex(range::range(&b"null"[..]).map(|_| BorrowedValue::Null)),
+        ))
+    }
+}
+
+fn object<'a, I>(depth: usize) -> impl Parser<Input = I, Output = BorrowedMap<'a>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let field = (
+        json_string(),
+        lex(byte(b':')),
+        json_value_(depth.saturating_sub(1)),
+    )
+        .map(|t| (t.0, t.2));
+    let fields = sep_by(field, lex(byte(b',')));
+    between(open(b'{', depth), lex(byte(b'}')), fields).expected("object")
+}
+
+fn array<'a, I>(depth: usize) -> impl Parser<Input = I, Output = Vec<BorrowedValue<'a>>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    between(
+        open(b'[', depth),
+        lex(byte(b']')),
+        sep_by(json_value_(depth.saturating_sub(1)), lex(byte(b','))),
+    )
+    .expected("array")
+}
+
+/// [`string_literal`](crate::parser::string_literal) borrowing from the input unless it has
+/// escapes, followed by whitespace
+fn json_string<'a, I>() -> impl Parser<Input = I, Output = Cow<'a, str>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let inner =
+        string_contents().and_then(|s| unescape(s).map_err(StreamErrorFor::<I>::message_message));
+    lex(between(byte(b'"'), byte(b'"'), inner).expected("string"))
+}
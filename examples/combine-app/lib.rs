@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate combine;
+
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use combine::easy;
+use combine::Parser as _;
+use json_model::{JsonParser, ParseError, Value, MAX_DEPTH};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Combine {
+    pub max_depth: usize,
+}
+
+impl Combine {
+    pub const fn new(max_depth: usize) -> Self {
+        Combine { max_depth }
+    }
+}
+
+impl Default for Combine {
+    fn default() -> Self {
+        Combine::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Combine {
+    fn name(&self) -> &'static str {
+        "combine"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        parser::json_value(self.max_depth)
+            .easy_parse(src.as_bytes())
+            .map(|(json, _rest)| json)
+            .map_err(|err| parse_error(src, err))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Combine {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        use combine::stream::state::{IndexPositioner, State};
+
+        spanned::json_value(self.max_depth)
+            .easy_parse(State::with_positioner(
+                src.as_bytes(),
+                IndexPositioner::new(),
+            ))
+            .map(|(json, _rest)| json)
+            .map_err(located_error)
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Combine {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        borrowed::json_value(self.max_depth)
+            .easy_parse(src.as_bytes())
+            .map(|(json, _rest)| json)
+            .map_err(|err| parse_error(src, err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Combine {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        ordered::json_value(keys, self.max_depth)
+            .easy_parse(src.as_bytes())
+            .map(|(json, _rest)| json)
+            .map_err(|err| parse_error(src, err))
+    }
+}
+
+pub fn parse_error(src: &str, err: easy::ParseError<&[u8]>) -> ParseError {
+    located_error(err.map_position(|p| p.translate_position(src.as_bytes())))
+}
+
+/// [`parse_error`] once positions are offsets into the input
+fn located_error(err: easy::Errors<u8, &[u8], usize>) -> ParseError {
+    let err = err
+        .map_token(char::from)
+        .map_range(|r| String::from_utf8_lossy(r).into_owned());
+    let expected = err
+        .errors
+        .iter()
+        .filter_map(|e| match e {
+            easy::Error::Expected(info) => Some(info.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    ParseError::new(err.to_string())
+        .with_offset(err.position)
+        .with_expected(expected)
+}
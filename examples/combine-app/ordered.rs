@@ -0,0 +1,87 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use combine::error::{ParseError, StreamError};
+use combine::stream::StreamErrorFor;
+use combine::{Parser, RangeStream};
+
+use combine::parser::byte::{byte, spaces};
+use combine::parser::choice::choice;
+use combine::parser::item::eof;
+use combine::parser::range;
+use combine::parser::repeat::sep_by;
+use combine::parser::sequence::between;
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+
+use crate::parser::{lex, number_literal, open, string_literal};
+
+/// Repeated keys in an object are handled as `keys` says, and at most `max_depth` arrays and
+/// objects may nest
+#[inline(always)]
+pub fn json_value<'a, I>(
+    keys: DuplicateKeys,
+    max_depth: usize,
+) -> impl Parser<Input = I, Output = OrderedValue> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    spaces().with(json_value_(keys, max_depth)).skip(eof())
+}
+
+parser! {
+    #[inline(always)]
+    fn json_value_['a, I](keys: DuplicateKeys, depth: usize)(I) -> OrderedValue
+        where [ I: RangeStream<Item = u8, Range = &'a [u8]> + 'a ]
+    {
+        choice((
+            lex(string_literal()).map(OrderedValue::String),
+            object(*keys, *depth).map(OrderedValue::Object),
+            array(*keys, *depth).map(OrderedValue::Array),
+            lex(number_literal()).map(OrderedValue::Number),
+            lex(range::range(&b"false"[..]).map(|_| OrderedValue::Bool(false))),
+            lex(range::range(&b"true"[..]).map(|_| OrderedValue::Bool(true))),
+            lex(range::range(&b"null"[..]).map(|_| OrderedValue::Null)),
+        ))
+    }
+}
+
+fn object<'a, I>(
+    keys: DuplicateKeys,
+    depth: usize,
+) -> impl Parser<Input = I, Output = OrderedMap> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let field = (
+        lex(string_literal()),
+        lex(byte(b':')),
+        json_value_(keys, depth.saturating_sub(1)),
+    )
+        .map(|t| (t.0, t.2));
+    let fields = sep_by(field, lex(byte(b',')));
+    between(open(b'{', depth), lex(byte(b'}')), fields)
+        .and_then(move |members: OrderedMap| {
+            keys.check(&members)
+                .map(|()| members)
+                .map_err(StreamErrorFor::<I>::message_message)
+        })
+        .expected("object")
+}
+
+fn array<'a, I>(
+    keys: DuplicateKeys,
+    depth: usize,
+) -> impl Parser<Input = I, Output = Vec<OrderedValue>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    between(
+        open(b'[', depth),
+        lex(byte(b']')),
+        sep_by(json_value_(keys, depth.saturating_sub(1)), lex(byte(b','))),
+    )
+    .expected("array")
+}
@@ -1,46 +1,42 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
 
-use combine::error::ParseError;
+use combine::error::{ParseError, StreamError};
+use combine::stream::StreamErrorFor;
 use combine::{Parser, RangeStream, StreamOnce};
 
-use combine::parser::byte::{byte, spaces};
+use combine::parser::byte::{byte, hex_digit, spaces};
 use combine::parser::choice::{choice, optional};
 use combine::parser::combinator::no_partial;
-use combine::parser::item::{one_of, satisfy_map};
+use combine::parser::item::{eof, one_of};
 use combine::parser::range;
-use combine::parser::repeat::{escaped, sep_by};
+use combine::parser::repeat::{escaped, sep_by, skip_count_min_max};
 use combine::parser::sequence::between;
 
-#[derive(PartialEq, Debug)]
-pub enum Value {
-    Number(f64),
-    String(String),
-    Bool(bool),
-    Null,
-    Object(HashMap<String, Value>),
-    Array(Vec<Value>),
-}
+use json_model::escape::unescape;
+use json_model::{Map, Value};
 
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
 #[inline(always)]
-pub fn json_value<'a, I>() -> impl Parser<Input = I, Output = Value> + 'a
+pub fn json_value<'a, I>(max_depth: usize) -> impl Parser<Input = I, Output = Value> + 'a
 where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
-    spaces().with(json_value_())
+    spaces().with(json_value_(max_depth)).skip(eof())
 }
 
 // We need to use `parser!` to break the recursive use of `value` to prevent the returned parser
 // from containing itself
 parser! {
     #[inline(always)]
-    fn json_value_['a, I]()(I) -> Value
+    fn json_value_['a, I](depth: usize)(I) -> Value
         where [ I: RangeStream<Item = u8, Range = &'a [u8]> + 'a ]
     {
         choice((
             json_string().map(Value::String),
-            object().map(Value::Object),
-            array().map(Value::Array),
+            object(*depth).map(Value::Object),
+            array(*depth).map(Value::Array),
             number().map(Value::Number),
             lex(range::range(&b"false"[..]).map(|_| Value::Bool(false))),
             lex(range::range(&b"true"[..]).map(|_| Value::Bool(true))),
@@ -49,70 +45,119 @@ parser! {
     }
 }
 
-fn object<'a, I>() -> impl Parser<Input = I, Output = HashMap<String, Value>> + 'a
+fn object<'a, I>(depth: usize) -> impl Parser<Input = I, Output = Map> + 'a
 where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
-    let field = (json_string(), lex(byte(b':')), json_value_()).map(|t| (t.0, t.2));
+    let field = (
+        json_string(),
+        lex(byte(b':')),
+        json_value_(depth.saturating_sub(1)),
+    )
+        .map(|t| (t.0, t.2));
     let fields = sep_by(field, lex(byte(b',')));
-    between(lex(byte(b'{')), lex(byte(b'}')), fields).expected("object")
+    between(open(b'{', depth), lex(byte(b'}')), fields).expected("object")
 }
 
-fn array<'a, I>() -> impl Parser<Input = I, Output = Vec<Value>> + 'a
+fn array<'a, I>(depth: usize) -> impl Parser<Input = I, Output = Vec<Value>> + 'a
 where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
     between(
-        lex(byte(b'[')),
+        open(b'[', depth),
         lex(byte(b']')),
-        sep_by(json_value_(), lex(byte(b','))),
+        sep_by(json_value_(depth.saturating_sub(1)), lex(byte(b','))),
     )
     .expected("array")
 }
 
+/// `bracket`, opening an array or object with `depth` levels of nesting left
+///
+/// Fails once `depth` is used up, so that deep input is rejected instead of overflowing the stack.
+/// Callers parse what's inside with `depth - 1` levels, saturating at 0 since nothing is parsed
+/// there when this fails.
+pub(crate) fn open<'a, I>(bracket: u8, depth: usize) -> impl Parser<Input = I, Output = u8> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    lex(byte(bracket)).and_then(move |bracket| {
+        if depth == 0 {
+            Err(StreamErrorFor::<I>::message_static_message(
+                "nesting too deep",
+            ))
+        } else {
+            Ok(bracket)
+        }
+    })
+}
+
 fn json_string<'a, I>() -> impl Parser<Input = I, Output = String> + 'a
 where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
-    let back_slash_byte = satisfy_map(|c| {
-        Some(match c {
-            b'"' => b'"',
-            b'\\' => b'\\',
-            b'/' => b'/',
-            b'b' => '\u{0008}' as u8,
-            b'f' => '\u{000c}' as u8,
-            b'n' => b'\n',
-            b'r' => b'\r',
-            b't' => b'\t',
-            _ => return None,
-        })
+    lex(string_literal())
+}
+
+/// A string without the whitespace after it
+pub(crate) fn string_literal<'a, I>() -> impl Parser<Input = I, Output = String> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let inner = string_contents().and_then(|s| {
+        unescape(s)
+            .map(Cow::into_owned)
+            .map_err(StreamErrorFor::<I>::message_message)
     });
-    let inner = range::recognize(escaped(
+    between(byte(b'"'), byte(b'"'), inner).expected("string")
+}
+
+/// The text between a string's quotes, with its escapes checked but not decoded
+pub(crate) fn string_contents<'a, I>() -> impl Parser<Input = I, Output = &'a str> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let escape = choice((
+        one_of("\"\\/bfnrt".bytes()).map(|_| ()),
+        byte(b'u').with(skip_count_min_max(4, 4, hex_digit())),
+    ));
+    range::recognize(escaped(
         range::take_while1(|b| b != b'\\' && b != b'"'),
         b'\\',
-        back_slash_byte,
+        escape,
     ))
-    .map(|s| std::str::from_utf8(s).unwrap().to_owned());
-    between(byte(b'"'), lex(byte(b'"')), inner).expected("string")
+    .map(|s: &'a [u8]| std::str::from_utf8(s).unwrap())
 }
 
 fn number<'a, I>() -> impl Parser<Input = I, Output = f64> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    lex(number_literal())
+}
+
+/// A number without the whitespace after it
+pub(crate) fn number_literal<'a, I>() -> impl Parser<Input = I, Output = f64> + 'a
 where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
     no_partial(
-        lex(range::recognize(no_partial((
+        range::recognize(no_partial((
             optional(one_of("+-".bytes())),
-            byte(b'0').or((digits(), optional((byte(b'.'), digits()))).map(|_| b'0')),
+            byte(b'0').or(digits().map(|_| b'0')),
+            optional((byte(b'.'), digits())),
             optional((
                 (one_of("eE".bytes()), optional(one_of("+-".bytes()))),
                 digits(),
             )),
-        ))))
+        )))
         .map(|s: &'a [u8]| std::str::from_utf8(s).unwrap().parse().unwrap())
         .expected("number"),
     )
@@ -123,10 +168,10 @@ where
     I: RangeStream<Item = u8, Range = &'a [u8]> + 'a,
     I::Error: ParseError<I::Item, I::Range, I::Position>,
 {
-    range::take_while1(|b| b >= b'0' && b <= b'9')
+    range::take_while1(|b: u8| b.is_ascii_digit())
 }
 
-fn lex<'a, P>(p: P) -> impl Parser<Input = P::Input, Output = P::Output>
+pub(crate) fn lex<'a, P>(p: P) -> impl Parser<Input = P::Input, Output = P::Output>
 where
     P: Parser,
     P::Input: RangeStream<Item = u8, Range = &'a [u8]>,
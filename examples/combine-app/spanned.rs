@@ -0,0 +1,87 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value
+//!
+//! Spans come from [`position`], so the input must track offsets, as
+//! [`State`](combine::stream::state::State) with an
+//! [`IndexPositioner`](combine::stream::state::IndexPositioner) does.
+
+use combine::error::ParseError;
+use combine::{Parser, RangeStream};
+
+use combine::parser::byte::{byte, spaces};
+use combine::parser::choice::choice;
+use combine::parser::item::{eof, position};
+use combine::parser::range;
+use combine::parser::repeat::sep_by;
+use combine::parser::sequence::between;
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+
+use crate::parser::{lex, number_literal, open, string_literal};
+
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
+#[inline(always)]
+pub fn json_value<'a, I>(
+    max_depth: usize,
+) -> impl Parser<Input = I, Output = Spanned<SpannedValue>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8], Position = usize> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    spaces().with(json_value_(max_depth)).skip(eof())
+}
+
+parser! {
+    #[inline(always)]
+    fn json_value_['a, I](depth: usize)(I) -> Spanned<SpannedValue>
+        where [ I: RangeStream<Item = u8, Range = &'a [u8], Position = usize> + 'a ]
+    {
+        lex(spanned(choice((
+            string_literal().map(SpannedValue::String),
+            object(*depth).map(SpannedValue::Object),
+            array(*depth).map(SpannedValue::Array),
+            number_literal().map(SpannedValue::Number),
+            range::range(&b"false"[..]).map(|_| SpannedValue::Bool(false)),
+            range::range(&b"true"[..]).map(|_| SpannedValue::Bool(true)),
+            range::range(&b"null"[..]).map(|_| SpannedValue::Null),
+        ))))
+    }
+}
+
+/// `p`'s output with the offsets before and after it
+fn spanned<'a, P>(p: P) -> impl Parser<Input = P::Input, Output = Spanned<P::Output>>
+where
+    P: Parser,
+    P::Input: RangeStream<Item = u8, Range = &'a [u8], Position = usize>,
+    <P::Input as combine::StreamOnce>::Error: ParseError<u8, &'a [u8], usize>,
+{
+    (position(), p, position()).map(|(start, value, end)| Spanned::new(value, start..end))
+}
+
+fn object<'a, I>(depth: usize) -> impl Parser<Input = I, Output = Vec<SpannedMember>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8], Position = usize> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    let field = (
+        lex(spanned(string_literal())),
+        lex(byte(b':')),
+        json_value_(depth.saturating_sub(1)),
+    )
+        .map(|t| (t.0, t.2));
+    let fields = sep_by(field, lex(byte(b',')));
+    between(open(b'{', depth), byte(b'}'), fields).expected("object")
+}
+
+fn array<'a, I>(depth: usize) -> impl Parser<Input = I, Output = Vec<Spanned<SpannedValue>>> + 'a
+where
+    I: RangeStream<Item = u8, Range = &'a [u8], Position = usize> + 'a,
+    I::Error: ParseError<I::Item, I::Range, I::Position>,
+{
+    between(
+        open(b'[', depth),
+        byte(b']'),
+        sep_by(json_value_(depth.saturating_sub(1)), lex(byte(b','))),
+    )
+    .expected("array")
+}
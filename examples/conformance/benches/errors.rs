@@ -0,0 +1,106 @@
+//! Time every parser takes to reject each document in `malformed/`
+//!
+//! Run with `cargo bench -p conformance --bench errors`; the offset scores alongside it are
+//! also checked by `tests/errors.rs`.
+
+use std::time::{Duration, Instant};
+
+use conformance::{ErrorScore, ErrorSummary, Malformed, PARSERS, REFERENCE};
+use json_model::JsonParser;
+
+const MIN_RUNS: usize = 10;
+const MAX_RUNS: usize = 10_000;
+const MIN_TIME: Duration = Duration::from_millis(20);
+
+/// Median time for `parser` to reject `case`, `None` if it doesn't
+fn time_to_fail(parser: &dyn JsonParser, case: &Malformed) -> Option<Duration> {
+    let mut times = Vec::new();
+    let mut spent = Duration::ZERO;
+    while times.len() < MIN_RUNS || (spent < MIN_TIME && times.len() < MAX_RUNS) {
+        let start = Instant::now();
+        let outcome = conformance::run(parser, std::hint::black_box(&case.src));
+        let elapsed = start.elapsed();
+        if !matches!(
+            ErrorScore::of(&outcome, case.fault),
+            ErrorScore::NoOffset | ErrorScore::Distance(_)
+        ) {
+            return None;
+        }
+        spent += elapsed;
+        times.push(elapsed);
+    }
+    times.sort_unstable();
+    Some(times[times.len() / 2])
+}
+
+fn fmt_time(time: Option<Duration>) -> String {
+    match time {
+        Some(time) if time < Duration::from_millis(1) => {
+            format!("{:.1}µs", time.as_secs_f64() * 1e6)
+        }
+        Some(time) => format!("{:.1}ms", time.as_secs_f64() * 1e3),
+        None => "-".to_owned(),
+    }
+}
+
+fn main() {
+    // Some parsers panic on some cases; `conformance::run` reports those without the noise
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let cases = conformance::malformed();
+    let mut parsers = PARSERS
+        .iter()
+        .copied()
+        .chain([REFERENCE])
+        .collect::<Vec<_>>();
+    parsers.sort_by_key(|parser| parser.name());
+
+    let mut rows = Vec::new();
+    println!(
+        "| parser | rejected | exact offset | within {} bytes | median distance | median time to fail |",
+        ErrorSummary::NEAR
+    );
+    println!("|---|---|---|---|---|---|");
+    for parser in &parsers {
+        let scores = cases
+            .iter()
+            .map(|case| ErrorScore::of(&conformance::run(*parser, &case.src), case.fault))
+            .collect::<Vec<_>>();
+        let times = cases
+            .iter()
+            .map(|case| time_to_fail(*parser, case))
+            .collect::<Vec<_>>();
+
+        let summary = ErrorSummary::new(&scores);
+        let mut failed = times.iter().flatten().copied().collect::<Vec<_>>();
+        failed.sort_unstable();
+        println!(
+            "| {} | {}/{} | {} | {} | {} | {} |",
+            parser.name(),
+            summary.rejected,
+            summary.cases,
+            summary.exact,
+            summary.near,
+            summary
+                .median_distance
+                .map_or_else(|| "-".to_owned(), |d| d.to_string()),
+            fmt_time(failed.get(failed.len() / 2).copied()),
+        );
+        rows.push(times);
+    }
+
+    println!();
+    let names = parsers
+        .iter()
+        .map(|parser| parser.name())
+        .collect::<Vec<_>>();
+    println!("| case | {} |", names.join(" | "));
+    println!("|---|{}", "---|".repeat(names.len()));
+    for (i, case) in cases.iter().enumerate() {
+        let cells = rows
+            .iter()
+            .map(|times| fmt_time(times[i]))
+            .collect::<Vec<_>>();
+        println!("| {} | {} |", case.name, cells.join(" | "));
+    }
+}
@@ -0,0 +1,70 @@
+//! What `grmtools`' CPCT+ error recovery costs over stopping at the first error, on each document
+//! in `malformed/`
+//!
+//! Run with `cargo bench -p conformance --bench recovery`. Alongside the times are how many errors
+//! CPCT+ reported and whether it repaired the document into a value.
+
+use std::time::{Duration, Instant};
+
+use grmtools_app::recover::{self, Recovery};
+
+const MIN_RUNS: usize = 10;
+const MAX_RUNS: usize = 10_000;
+const MIN_TIME: Duration = Duration::from_millis(20);
+
+/// Median time to parse `src` with `recovery`
+fn time(src: &str, recovery: Recovery) -> Duration {
+    let mut times = Vec::new();
+    let mut spent = Duration::ZERO;
+    while times.len() < MIN_RUNS || (spent < MIN_TIME && times.len() < MAX_RUNS) {
+        let start = Instant::now();
+        std::hint::black_box(recover::parse(std::hint::black_box(src), recovery));
+        let elapsed = start.elapsed();
+        spent += elapsed;
+        times.push(elapsed);
+    }
+    times.sort_unstable();
+    times[times.len() / 2]
+}
+
+fn fmt_time(time: Duration) -> String {
+    if time < Duration::from_millis(1) {
+        format!("{:.1}µs", time.as_secs_f64() * 1e6)
+    } else {
+        format!("{:.1}ms", time.as_secs_f64() * 1e3)
+    }
+}
+
+fn main() {
+    let cases = conformance::malformed();
+
+    println!("| case | no recovery | CPCT+ | errors | repaired |");
+    println!("|---|---|---|---|---|");
+    let (mut none_total, mut cpct_total, mut repaired) = (Duration::ZERO, Duration::ZERO, 0);
+    for case in &cases {
+        let none = time(&case.src, Recovery::None);
+        let cpct = time(&case.src, Recovery::CpctPlus);
+        let recovered = recover::parse(&case.src, Recovery::CpctPlus);
+        none_total += none;
+        cpct_total += cpct;
+        repaired += usize::from(recovered.value.is_some());
+        println!(
+            "| {} | {} | {} | {} | {} |",
+            case.name,
+            fmt_time(none),
+            fmt_time(cpct),
+            recovered.errors.len(),
+            if recovered.value.is_some() {
+                "yes"
+            } else {
+                "no"
+            },
+        );
+    }
+    println!(
+        "| total | {} | {} | | {repaired}/{} |",
+        fmt_time(none_total),
+        fmt_time(cpct_total),
+        cases.len()
+    );
+}
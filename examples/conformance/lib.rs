@@ -0,0 +1,462 @@
+//! Run every example parser over the same documents
+//!
+//! The parsers are called in-process through their library targets and their results compared
+//! as canonical [`Value`]s, with `serde_json` as the reference implementation.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use json_model::{
+    BorrowedParser, DuplicateKeys, IntoValue as _, JsonParser, OrderedParser, ParseError,
+    RawNumberParser, SpannedParser, Value, MAX_DEPTH,
+};
+
+/// The implementation every other parser is compared against
+///
+/// It is built with `serde_json`'s `float_roundtrip` feature, so its numbers are the nearest
+/// `f64` to their text.
+pub const REFERENCE: &dyn JsonParser = &serde_json_app::SerdeJson;
+
+/// Every parser other than [`REFERENCE`]
+pub const PARSERS: &[&dyn JsonParser] = &[
+    &chumsky_app::Chumsky::new(MAX_DEPTH),
+    &combine_app::Combine::new(MAX_DEPTH),
+    &grmtools_app::Grmtools,
+    &lalrpop_app::Lalrpop,
+    &lelwel_app::Lelwel,
+    &logos_app::Logos::new(MAX_DEPTH),
+    &nom_app::Nom::new(MAX_DEPTH),
+    &parol_app::Parol::new(MAX_DEPTH),
+    &peg_app::Peg::new(MAX_DEPTH),
+    &pest_app::Pest::new(MAX_DEPTH),
+    &winnow_app::Winnow::new(MAX_DEPTH),
+    &yap_app::Yap::new(MAX_DEPTH),
+];
+
+/// Every parser that implements [`SpannedParser`]
+///
+/// `serde_json` has no span API.
+pub const SPANNED: &[&dyn SpannedParser] = &[
+    &chumsky_app::Chumsky::new(MAX_DEPTH),
+    &combine_app::Combine::new(MAX_DEPTH),
+    &grmtools_app::Grmtools,
+    &lalrpop_app::Lalrpop,
+    &lelwel_app::Lelwel,
+    &logos_app::Logos::new(MAX_DEPTH),
+    &nom_app::Nom::new(MAX_DEPTH),
+    &parol_app::Parol::new(MAX_DEPTH),
+    &peg_app::Peg::new(MAX_DEPTH),
+    &pest_app::Pest::new(MAX_DEPTH),
+    &winnow_app::Winnow::new(MAX_DEPTH),
+    &yap_app::Yap::new(MAX_DEPTH),
+];
+
+/// Every parser that implements [`BorrowedParser`], [`REFERENCE`] included
+pub const BORROWED: &[&dyn BorrowedParser] = &[
+    &chumsky_app::Chumsky::new(MAX_DEPTH),
+    &combine_app::Combine::new(MAX_DEPTH),
+    &grmtools_app::Grmtools,
+    &lalrpop_app::Lalrpop,
+    &lelwel_app::Lelwel,
+    &logos_app::Logos::new(MAX_DEPTH),
+    &nom_app::Nom::new(MAX_DEPTH),
+    &parol_app::Parol::new(MAX_DEPTH),
+    &peg_app::Peg::new(MAX_DEPTH),
+    &pest_app::Pest::new(MAX_DEPTH),
+    &serde_json_app::SerdeJson,
+    &winnow_app::Winnow::new(MAX_DEPTH),
+    &yap_app::Yap::new(MAX_DEPTH),
+];
+
+/// Every parser that implements [`RawNumberParser`]
+///
+/// Only those whose grammar already has each number's text as a slice. `serde_json` captures a
+/// `RawValue` rather than enable `arbitrary_precision`, which would also change [`REFERENCE`].
+pub const RAW_NUMBERS: &[&dyn RawNumberParser] = &[
+    &chumsky_app::Chumsky::new(MAX_DEPTH),
+    &grmtools_app::Grmtools,
+    &lalrpop_app::Lalrpop,
+    &lelwel_app::Lelwel,
+    &logos_app::Logos::new(MAX_DEPTH),
+    &peg_app::Peg::new(MAX_DEPTH),
+    &pest_app::Pest::new(MAX_DEPTH),
+    &serde_json_app::SerdeJson,
+    &winnow_app::Winnow::new(MAX_DEPTH),
+    &yap_app::Yap::new(MAX_DEPTH),
+];
+
+/// Every parser that implements [`OrderedParser`], [`REFERENCE`] included
+pub const ORDERED: &[&dyn OrderedParser] = &[
+    &chumsky_app::Chumsky::new(MAX_DEPTH),
+    &combine_app::Combine::new(MAX_DEPTH),
+    &grmtools_app::Grmtools,
+    &lalrpop_app::Lalrpop,
+    &lelwel_app::Lelwel,
+    &logos_app::Logos::new(MAX_DEPTH),
+    &nom_app::Nom::new(MAX_DEPTH),
+    &parol_app::Parol::new(MAX_DEPTH),
+    &peg_app::Peg::new(MAX_DEPTH),
+    &pest_app::Pest::new(MAX_DEPTH),
+    &serde_json_app::SerdeJson,
+    &winnow_app::Winnow::new(MAX_DEPTH),
+    &yap_app::Yap::new(MAX_DEPTH),
+];
+
+/// What became of a parse attempt
+#[derive(Debug)]
+pub enum Outcome {
+    Accepted(Value),
+    Rejected(ParseError),
+    Panicked(String),
+}
+
+impl Outcome {
+    /// What the parser did, for reports
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Outcome::Accepted(_) => "accepted",
+            Outcome::Rejected(_) => "rejected",
+            Outcome::Panicked(_) => "panicked",
+        }
+    }
+}
+
+/// Parse `src` with `parser`, turning a panic into [`Outcome::Panicked`]
+pub fn run(parser: &dyn JsonParser, src: &str) -> Outcome {
+    catch(|| parser.parse(src))
+}
+
+/// [`run`] through [`SpannedParser::parse_spanned`], dropping the spans
+pub fn run_spanned(parser: &dyn SpannedParser, src: &str) -> Outcome {
+    catch(|| parser.parse_spanned(src).map(|value| value.into_value()))
+}
+
+/// [`run`] through [`BorrowedParser::parse_borrowed`]
+pub fn run_borrowed(parser: &dyn BorrowedParser, src: &str) -> Outcome {
+    catch(|| parser.parse_borrowed(src).map(|value| value.into_value()))
+}
+
+/// [`run`] through [`RawNumberParser::parse_raw_numbers`], rounding the numbers
+pub fn run_raw_numbers(parser: &dyn RawNumberParser, src: &str) -> Outcome {
+    catch(|| {
+        parser
+            .parse_raw_numbers(src)
+            .map(|value| value.into_value())
+    })
+}
+
+/// [`run`] through [`OrderedParser::parse_ordered`], of duplicate keys the last winning
+pub fn run_ordered(parser: &dyn OrderedParser, src: &str, keys: DuplicateKeys) -> Outcome {
+    catch(|| {
+        parser
+            .parse_ordered(src, keys)
+            .map(|value| value.into_value())
+    })
+}
+
+/// Parse `src` with every parser in every mode it has, [`REFERENCE`] included
+///
+/// Each outcome comes with the parser's name and the mode's, as in `--mode`.
+pub fn every_mode(src: &str) -> Vec<(&'static str, &'static str, Outcome)> {
+    [REFERENCE]
+        .iter()
+        .chain(PARSERS)
+        .map(|parser| (parser.name(), "plain", run(*parser, src)))
+        .chain(
+            SPANNED
+                .iter()
+                .map(|parser| (parser.name(), "spanned", run_spanned(*parser, src))),
+        )
+        .chain(
+            BORROWED
+                .iter()
+                .map(|parser| (parser.name(), "borrowed", run_borrowed(*parser, src))),
+        )
+        .chain(
+            RAW_NUMBERS
+                .iter()
+                .map(|parser| (parser.name(), "raw-numbers", run_raw_numbers(*parser, src))),
+        )
+        .chain(ORDERED.iter().flat_map(|parser| {
+            [
+                ("ordered", DuplicateKeys::Keep),
+                ("unique-keys", DuplicateKeys::Reject),
+            ]
+            .map(|(mode, keys)| (parser.name(), mode, run_ordered(*parser, src, keys)))
+        }))
+        .collect()
+}
+
+fn catch(parse: impl FnOnce() -> Result<Value, ParseError>) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(parse)) {
+        Ok(Ok(value)) => Outcome::Accepted(value),
+        Ok(Err(err)) => Outcome::Rejected(err),
+        Err(payload) => {
+            let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+                (*msg).to_owned()
+            } else if let Some(msg) = payload.downcast_ref::<String>() {
+                msg.clone()
+            } else {
+                "<unknown>".to_owned()
+            };
+            Outcome::Panicked(msg)
+        }
+    }
+}
+
+/// Load all `*.json` documents from `dir` (relative to this crate), sorted by file name
+pub fn corpus(dir: &str) -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(dir);
+    let mut docs = std::fs::read_dir(&dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let src = std::fs::read_to_string(&path).unwrap();
+            (name, src)
+        })
+        .collect::<Vec<_>>();
+    docs.sort();
+    docs
+}
+
+/// Directory names of every `examples/*-app`
+pub fn apps() -> Vec<String> {
+    let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut apps = std::fs::read_dir(examples)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|dir| dir.ends_with("-app"))
+        .collect::<Vec<_>>();
+    apps.sort();
+    apps
+}
+
+/// Build the binaries of `apps`, returning the directory holding them
+///
+/// They are built with the calling test's profile, or with `release` if `release` is set, in the
+/// same target directory.
+pub fn build_apps(apps: &[String], release: bool) -> PathBuf {
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.arg("build").arg("--bins").stdin(Stdio::null());
+    if release {
+        cargo.arg("--release");
+    }
+    for app in apps {
+        cargo.args(["--package", app]);
+    }
+    let status = cargo.status().unwrap();
+    assert!(status.success(), "building the apps failed: {status}");
+
+    // Tests run from `<target>/<profile>/deps/`
+    let exe = std::env::current_exe().unwrap();
+    let profile_dir = exe.parent().unwrap().parent().unwrap();
+    if release {
+        profile_dir.parent().unwrap().join("release")
+    } else {
+        profile_dir.to_owned()
+    }
+}
+
+/// Describe the first place where `actual` differs from `expected`
+pub fn diff(expected: &Value, actual: &Value) -> Option<String> {
+    diff_at("$", expected, actual)
+}
+
+/// Describe how a parser's `mode` strays from its plain mode on one document
+///
+/// Both must accept, reject or panic alike, and accepted values must agree. Only then is
+/// `accepted` called with the plain value to check what is particular to the mode.
+pub fn compare_modes(
+    mode: &str,
+    plain: &Outcome,
+    outcome: &Outcome,
+    accepted: impl FnOnce(&Value) -> Option<String>,
+) -> Option<String> {
+    match (plain, outcome) {
+        (Outcome::Accepted(plain), Outcome::Accepted(value)) => match diff(plain, value) {
+            Some(diff) => Some(format!("{mode} value differs, {diff}")),
+            None => accepted(plain),
+        },
+        (Outcome::Rejected(_), Outcome::Rejected(_))
+        | (Outcome::Panicked(_), Outcome::Panicked(_)) => None,
+        (plain, outcome) => Some(format!(
+            "plain mode {}, {mode} mode {}",
+            plain.verb(),
+            outcome.verb()
+        )),
+    }
+}
+
+/// Describe how `actual` falls short of `expected`: a [`diff`] if it was accepted, else why not
+pub fn diff_outcome(expected: &Value, actual: &Outcome) -> Option<String> {
+    match actual {
+        Outcome::Accepted(actual) => diff(expected, actual),
+        Outcome::Rejected(err) => Some(format!("rejected: {}", summarize(&err.message))),
+        Outcome::Panicked(msg) => Some(format!("panicked: {}", summarize(msg))),
+    }
+}
+
+fn diff_at(path: &str, expected: &Value, actual: &Value) -> Option<String> {
+    match (expected, actual) {
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() != actual.len() {
+                return Some(format!(
+                    "{path}: expected {} elements, got {}",
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            expected
+                .iter()
+                .zip(actual)
+                .enumerate()
+                .find_map(|(i, (e, a))| diff_at(&format!("{path}[{i}]"), e, a))
+        }
+        (Value::Object(expected), Value::Object(actual)) => {
+            let mut keys = expected.keys().chain(actual.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter()
+                .find_map(|key| match (expected.get(key), actual.get(key)) {
+                    (Some(e), Some(a)) => diff_at(&format!("{path}[{key:?}]"), e, a),
+                    (Some(_), None) => Some(format!("{path}: missing key {key:?}")),
+                    (None, Some(_)) => Some(format!("{path}: unexpected key {key:?}")),
+                    (None, None) => unreachable!(),
+                })
+        }
+        _ if expected == actual => None,
+        _ => Some(format!(
+            "{path}: expected {}, got {}",
+            summarize(&format!("{expected:?}")),
+            summarize(&format!("{actual:?}"))
+        )),
+    }
+}
+
+/// Single-line rendering of `msg`, short enough to keep reports readable
+pub fn summarize(msg: &str) -> String {
+    const MAX: usize = 60;
+    let msg = msg.lines().next().unwrap_or_default().trim();
+    if MAX < msg.chars().count() {
+        let msg = msg.chars().take(MAX).collect::<String>();
+        format!("{msg}...")
+    } else {
+        msg.to_owned()
+    }
+}
+
+/// The numbers in `numbers.txt`, in file order
+pub fn numbers() -> Vec<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("numbers.txt");
+    let numbers = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+    numbers
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// A broken document from `malformed/`
+pub struct Malformed {
+    pub name: String,
+    pub src: String,
+    /// Byte offset of the first byte no valid document could have, per `malformed/faults.txt`
+    pub fault: usize,
+}
+
+/// Load every case in `malformed/` with its expected fault offset
+pub fn malformed() -> Vec<Malformed> {
+    let faults_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("malformed/faults.txt");
+    let faults = std::fs::read_to_string(&faults_path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", faults_path.display()));
+    let faults = faults
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, fault) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("faults.txt: malformed line {line:?}"));
+            (name.to_owned(), fault.trim().parse::<usize>().unwrap())
+        })
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let docs = corpus("malformed");
+    assert_eq!(
+        docs.len(),
+        faults.len(),
+        "every case needs an entry in faults.txt"
+    );
+    docs.into_iter()
+        .map(|(name, src)| {
+            let fault = *faults
+                .get(&name)
+                .unwrap_or_else(|| panic!("{name}: missing from faults.txt"));
+            Malformed { name, src, fault }
+        })
+        .collect()
+}
+
+/// How well a parser pinpointed a [`Malformed`] document's fault
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorScore {
+    Accepted,
+    Panicked,
+    /// Rejected without saying where
+    NoOffset,
+    /// Rejected, reporting this many bytes after (or, if negative, before) the fault
+    Distance(isize),
+}
+
+impl ErrorScore {
+    pub fn of(outcome: &Outcome, fault: usize) -> Self {
+        match outcome {
+            Outcome::Accepted(_) => Self::Accepted,
+            Outcome::Panicked(_) => Self::Panicked,
+            Outcome::Rejected(err) => match err.offset {
+                Some(offset) => Self::Distance(offset as isize - fault as isize),
+                None => Self::NoOffset,
+            },
+        }
+    }
+}
+
+/// [`ErrorScore`]s of one parser across the `malformed/` cases
+pub struct ErrorSummary {
+    pub cases: usize,
+    pub rejected: usize,
+    /// Rejected, reporting the fault's exact offset
+    pub exact: usize,
+    /// Rejected, reporting an offset within [`ErrorSummary::NEAR`] bytes of the fault
+    pub near: usize,
+    /// Median of the absolute distances that were reported
+    pub median_distance: Option<usize>,
+}
+
+impl ErrorSummary {
+    pub const NEAR: usize = 4;
+
+    pub fn new(scores: &[ErrorScore]) -> Self {
+        let mut distances = scores
+            .iter()
+            .filter_map(|score| match score {
+                ErrorScore::Distance(d) => Some(d.unsigned_abs()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        distances.sort_unstable();
+        Self {
+            cases: scores.len(),
+            rejected: scores
+                .iter()
+                .filter(|score| matches!(score, ErrorScore::NoOffset | ErrorScore::Distance(_)))
+                .count(),
+            exact: distances.iter().filter(|d| **d == 0).count(),
+            near: distances.iter().filter(|d| **d <= Self::NEAR).count(),
+            median_distance: distances.get(distances.len() / 2).copied(),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Check every [`BorrowedParser`](json_model::BorrowedParser) against its plain mode
+//!
+//! Each parser's borrowed mode must accept and reject what its plain mode does and build the same
+//! value, and every string it borrows must point into the input. How many strings and keys each
+//! had to allocate over the corpus is recorded alongside any problems in
+//! `snapshots/borrowed.txt`; run with `SNAPSHOTS=overwrite` to accept a change in behavior.
+
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use conformance::BORROWED;
+use json_model::BorrowedValue;
+
+#[derive(Default)]
+struct Strings {
+    borrowed: usize,
+    owned: usize,
+}
+
+impl Strings {
+    /// Count `s`, describing it if it claims to borrow from outside `src`
+    #[allow(clippy::ptr_arg)] // Which variant `s` is matters
+    fn add(&mut self, src: &str, path: &str, s: &Cow<'_, str>) -> Option<String> {
+        match s {
+            Cow::Borrowed(s) => {
+                self.borrowed += 1;
+                let input = src.as_bytes().as_ptr_range();
+                let borrowed = s.as_bytes().as_ptr_range();
+                let inside = input.start <= borrowed.start && borrowed.end <= input.end;
+                (!s.is_empty() && !inside)
+                    .then(|| format!("{path}: {s:?} is borrowed from outside the input"))
+            }
+            Cow::Owned(_) => {
+                self.owned += 1;
+                None
+            }
+        }
+    }
+
+    /// Count every string and key in `value`, describing the first that isn't from `src`
+    fn add_all(&mut self, src: &str, path: &str, value: &BorrowedValue<'_>) -> Option<String> {
+        match value {
+            BorrowedValue::String(s) => self.add(src, path, s),
+            BorrowedValue::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| self.add_all(src, &format!("{path}[{i}]"), item))
+                .fold(None, Option::or),
+            BorrowedValue::Object(members) => {
+                let mut members = members.iter().collect::<Vec<_>>();
+                members.sort_by_key(|(key, _)| *key);
+                members
+                    .into_iter()
+                    .map(|(key, member)| {
+                        let path = format!("{path}[{key:?}]");
+                        let key = self.add(src, &format!("{path} key"), key);
+                        key.or(self.add_all(src, &path, member))
+                    })
+                    .fold(None, Option::or)
+            }
+            BorrowedValue::Null | BorrowedValue::Bool(_) | BorrowedValue::Number(_) => None,
+        }
+    }
+}
+
+#[test]
+fn borrowed() {
+    let corpus = conformance::corpus("corpus");
+    let malformed = conformance::malformed();
+
+    let mut report = String::new();
+    let mut parsers = BORROWED.to_vec();
+    parsers.sort_by_key(|parser| parser.name());
+    for parser in parsers {
+        let mut problems = Vec::new();
+        let mut strings = Strings::default();
+        let docs = corpus
+            .iter()
+            .map(|(name, src)| (name.as_str(), src.as_str(), true))
+            .chain(
+                malformed
+                    .iter()
+                    .map(|case| (case.name.as_str(), case.src.as_str(), false)),
+            );
+        for (name, src, counted) in docs {
+            let problem = conformance::compare_modes(
+                "borrowed",
+                &conformance::run(parser, src),
+                &conformance::run_borrowed(parser, src),
+                |_| {
+                    // Only parse again for the strings once the values agree
+                    let value = parser.parse_borrowed(src).unwrap();
+                    let mut doc_strings = Strings::default();
+                    let problem = doc_strings.add_all(src, "$", &value);
+                    if counted {
+                        strings.borrowed += doc_strings.borrowed;
+                        strings.owned += doc_strings.owned;
+                    }
+                    problem
+                },
+            );
+            if let Some(problem) = problem {
+                problems.push(format!("{name}: {problem}"));
+            }
+        }
+
+        let Strings { borrowed, owned } = strings;
+        write!(
+            report,
+            "{}: {borrowed} borrowed, {owned} owned",
+            parser.name()
+        )
+        .unwrap();
+        if problems.is_empty() {
+            writeln!(report).unwrap();
+        } else {
+            writeln!(report, ", {} problems", problems.len()).unwrap();
+            for problem in problems {
+                writeln!(report, "  {problem}").unwrap();
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/borrowed.txt"].raw());
+}
@@ -0,0 +1,140 @@
+//! Accept/reject behavior of every parser on the cases in `test_parsing/`
+//!
+//! Case names follow [JSONTestSuite](https://github.com/nst/JSONTestSuite):
+//! - `y_`: content that must be accepted
+//! - `n_`: content that must be rejected
+//! - `i_`: parsers are free to accept or reject the content
+//!
+//! The resulting matrix is recorded in `snapshots/compliance.md`; run with `SNAPSHOTS=overwrite`
+//! to accept a change in behavior.
+
+use std::fmt::Write as _;
+
+use conformance::{Outcome, PARSERS, REFERENCE};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Verdict {
+    Accept,
+    Reject,
+    Panic,
+}
+
+impl Verdict {
+    fn of(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Accepted(_) => Self::Accept,
+            Outcome::Rejected(_) => Self::Reject,
+            Outcome::Panicked(_) => Self::Panic,
+        }
+    }
+}
+
+/// Whether a verdict meets the expectation for `case`, `None` for implementation-defined cases
+fn conforms(case: &str, verdict: Verdict) -> Option<bool> {
+    match case.split_once('_') {
+        Some(("y", _)) => Some(verdict == Verdict::Accept),
+        Some(("n", _)) => Some(verdict == Verdict::Reject),
+        Some(("i", _)) => None,
+        _ => panic!("{case}: unknown expectation"),
+    }
+}
+
+fn cell(case: &str, verdict: Verdict) -> &'static str {
+    match (verdict, conforms(case, verdict)) {
+        (Verdict::Panic, _) => "PANIC",
+        (_, Some(true)) => "pass",
+        (_, Some(false)) => "FAIL",
+        (Verdict::Accept, None) => "accept",
+        (Verdict::Reject, None) => "reject",
+    }
+}
+
+#[test]
+fn compliance() {
+    let corpus = conformance::corpus("test_parsing");
+    let mut parsers = PARSERS
+        .iter()
+        .copied()
+        .chain([REFERENCE])
+        .collect::<Vec<_>>();
+    parsers.sort_by_key(|parser| parser.name());
+
+    let verdicts = corpus
+        .iter()
+        .map(|(_, src)| {
+            parsers
+                .iter()
+                .map(|parser| Verdict::of(conformance::run(*parser, src)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let reference = parsers
+        .iter()
+        .position(|parser| parser.name() == REFERENCE.name())
+        .unwrap();
+    for ((case, _), verdicts) in corpus.iter().zip(&verdicts) {
+        assert_ne!(
+            conforms(case, verdicts[reference]),
+            Some(false),
+            "{case}: {} disagrees with the expectation",
+            REFERENCE.name()
+        );
+    }
+
+    let mut report = String::new();
+    writeln!(report, "# Summary").unwrap();
+    writeln!(report).unwrap();
+    writeln!(
+        report,
+        "| parser | y_ accepted | n_ rejected | i_ accepted | panics |"
+    )
+    .unwrap();
+    writeln!(report, "|---|---|---|---|---|").unwrap();
+    for (i, parser) in parsers.iter().enumerate() {
+        let count = |prefix: &str, expected: Verdict| {
+            let cases = corpus
+                .iter()
+                .zip(&verdicts)
+                .filter(|((case, _), _)| case.starts_with(prefix))
+                .collect::<Vec<_>>();
+            let matching = cases
+                .iter()
+                .filter(|(_, verdicts)| verdicts[i] == expected)
+                .count();
+            format!("{matching}/{}", cases.len())
+        };
+        let panics = verdicts
+            .iter()
+            .filter(|verdicts| verdicts[i] == Verdict::Panic)
+            .count();
+        writeln!(
+            report,
+            "| {} | {} | {} | {} | {panics} |",
+            parser.name(),
+            count("y_", Verdict::Accept),
+            count("n_", Verdict::Reject),
+            count("i_", Verdict::Accept),
+        )
+        .unwrap();
+    }
+
+    writeln!(report).unwrap();
+    writeln!(report, "# Cases").unwrap();
+    writeln!(report).unwrap();
+    let names = parsers
+        .iter()
+        .map(|parser| parser.name())
+        .collect::<Vec<_>>();
+    writeln!(report, "| case | {} |", names.join(" | ")).unwrap();
+    writeln!(report, "|---|{}", "---|".repeat(names.len())).unwrap();
+    for ((case, _), verdicts) in corpus.iter().zip(&verdicts) {
+        let cells = verdicts
+            .iter()
+            .map(|verdict| cell(case, *verdict))
+            .collect::<Vec<_>>();
+        writeln!(report, "| {case} | {} |", cells.join(" | ")).unwrap();
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/compliance.md"].raw());
+}
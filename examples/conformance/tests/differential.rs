@@ -0,0 +1,38 @@
+//! Compare every parser's output with `serde_json` on the documents in `corpus/`
+//!
+//! Known divergences are recorded in `snapshots/differential.txt`; run with
+//! `SNAPSHOTS=overwrite` to accept a change in behavior.
+
+use std::fmt::Write as _;
+
+use conformance::{Outcome, PARSERS, REFERENCE};
+
+#[test]
+fn differential() {
+    let corpus = conformance::corpus("corpus");
+
+    let mut report = String::new();
+    for parser in PARSERS {
+        let mut mismatches = Vec::new();
+        for (name, src) in &corpus {
+            let Outcome::Accepted(expected) = conformance::run(REFERENCE, src) else {
+                panic!("{name}: rejected by {}", REFERENCE.name());
+            };
+            let mismatch = conformance::diff_outcome(&expected, &conformance::run(*parser, src));
+            if let Some(mismatch) = mismatch {
+                mismatches.push(format!("{name}: {mismatch}"));
+            }
+        }
+
+        if mismatches.is_empty() {
+            writeln!(report, "{}: ok", parser.name()).unwrap();
+        } else {
+            writeln!(report, "{}: {} mismatches", parser.name(), mismatches.len()).unwrap();
+            for mismatch in mismatches {
+                writeln!(report, "  {mismatch}").unwrap();
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/differential.txt"].raw());
+}
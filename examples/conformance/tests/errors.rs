@@ -0,0 +1,101 @@
+//! How precisely every parser locates the fault in the documents in `malformed/`
+//!
+//! Each case's expected offset is listed in `malformed/faults.txt`. The scores are recorded in
+//! `snapshots/errors.md`; run with `SNAPSHOTS=overwrite` to accept a change in behavior. Time to
+//! fail is measured separately by `cargo bench -p conformance --bench errors`.
+
+use std::fmt::Write as _;
+
+use conformance::{ErrorScore, ErrorSummary, Outcome, PARSERS, REFERENCE};
+
+fn cell(score: ErrorScore) -> String {
+    match score {
+        ErrorScore::Accepted => "ACCEPT".to_owned(),
+        ErrorScore::Panicked => "PANIC".to_owned(),
+        ErrorScore::NoOffset => "?".to_owned(),
+        ErrorScore::Distance(0) => "0".to_owned(),
+        ErrorScore::Distance(d) => format!("{d:+}"),
+    }
+}
+
+#[test]
+fn errors() {
+    let cases = conformance::malformed();
+    let mut parsers = PARSERS
+        .iter()
+        .copied()
+        .chain([REFERENCE])
+        .collect::<Vec<_>>();
+    parsers.sort_by_key(|parser| parser.name());
+
+    for case in &cases {
+        assert!(
+            !matches!(conformance::run(REFERENCE, &case.src), Outcome::Accepted(_)),
+            "{}: accepted by {}",
+            case.name,
+            REFERENCE.name()
+        );
+    }
+
+    let scores = parsers
+        .iter()
+        .map(|parser| {
+            cases
+                .iter()
+                .map(|case| ErrorScore::of(&conformance::run(*parser, &case.src), case.fault))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut report = String::new();
+    writeln!(report, "# Summary").unwrap();
+    writeln!(report).unwrap();
+    writeln!(
+        report,
+        "| parser | rejected | exact offset | within {} bytes | median distance |",
+        ErrorSummary::NEAR
+    )
+    .unwrap();
+    writeln!(report, "|---|---|---|---|---|").unwrap();
+    for (parser, scores) in parsers.iter().zip(&scores) {
+        let summary = ErrorSummary::new(scores);
+        let median = summary
+            .median_distance
+            .map_or_else(|| "-".to_owned(), |d| d.to_string());
+        writeln!(
+            report,
+            "| {} | {}/{} | {} | {} | {median} |",
+            parser.name(),
+            summary.rejected,
+            summary.cases,
+            summary.exact,
+            summary.near,
+        )
+        .unwrap();
+    }
+
+    writeln!(report).unwrap();
+    writeln!(report, "# Cases").unwrap();
+    writeln!(report).unwrap();
+    writeln!(
+        report,
+        "Bytes between the reported offset and the fault; `?` when no offset was reported."
+    )
+    .unwrap();
+    writeln!(report).unwrap();
+    let names = parsers
+        .iter()
+        .map(|parser| parser.name())
+        .collect::<Vec<_>>();
+    writeln!(report, "| case | {} |", names.join(" | ")).unwrap();
+    writeln!(report, "|---|{}", "---|".repeat(names.len())).unwrap();
+    for (i, case) in cases.iter().enumerate() {
+        let cells = scores
+            .iter()
+            .map(|scores| cell(scores[i]))
+            .collect::<Vec<_>>();
+        writeln!(report, "| {} | {} |", case.name, cells.join(" | ")).unwrap();
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/errors.md"].raw());
+}
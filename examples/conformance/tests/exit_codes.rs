@@ -0,0 +1,149 @@
+//! Every `<name>-app` binary follows the [`Exit`] code contract
+//!
+//! The binaries are built with the same profile and target directory as this test.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use conformance::Outcome;
+use json_model::cli::Exit;
+
+/// Documents that have crashed an app instead of being rejected
+const CRASHED: &[&str] = &[
+    "malformed/late_trailing_comma.json",
+    "test_parsing/n_number_0e.json",
+    "test_parsing/n_number_0_capital_E.json",
+    "test_parsing/n_number_1.0e.json",
+];
+
+fn exit_code(bin: &Path, args: &[&str]) -> Option<i32> {
+    Command::new(bin)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap()
+        .code()
+}
+
+#[test]
+fn exit_codes() {
+    let apps = conformance::apps();
+    let bin_dir = conformance::build_apps(&apps, false);
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let valid = root.join("corpus/nested.json");
+    let invalid = root.join("malformed/missing_comma.json");
+    let trailing = root.join("malformed/trailing_garbage.json");
+    let missing = root.join("malformed/does-not-exist.json");
+    let valid = valid.to_str().unwrap();
+    let invalid = invalid.to_str().unwrap();
+    let trailing = trailing.to_str().unwrap();
+    let missing = missing.to_str().unwrap();
+    let crashed = CRASHED
+        .iter()
+        .map(|doc| {
+            let path = root.join(doc);
+            let src = std::fs::read_to_string(&path).unwrap();
+            (*doc, path.to_str().unwrap().to_owned(), src)
+        })
+        .collect::<Vec<_>>();
+
+    let mut failures = Vec::new();
+    for app in &apps {
+        let bin = bin_dir.join(format!("{app}{}", std::env::consts::EXE_SUFFIX));
+        let mut cases = vec![
+            ("valid", vec![valid], Exit::Success),
+            (
+                "valid, --errors=json",
+                vec!["--errors=json", valid],
+                Exit::Success,
+            ),
+            ("missing file", vec![missing], Exit::Io),
+            ("no arguments", vec![], Exit::Usage),
+            (
+                "unknown error format",
+                vec!["--errors=xml", valid],
+                Exit::Usage,
+            ),
+            ("extra argument", vec![valid, valid], Exit::Usage),
+            ("unknown mode", vec!["--mode=tree", valid], Exit::Usage),
+            (
+                "invalid depth",
+                vec!["--max-depth=deep", valid],
+                Exit::Usage,
+            ),
+            // The apps are built without their `spanned`, `borrowed`, `raw-numbers`, `ordered` and
+            // `recover` features
+            (
+                "--mode=spanned, unsupported",
+                vec!["--mode=spanned", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=borrowed, unsupported",
+                vec!["--mode=borrowed", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=raw-numbers, unsupported",
+                vec!["--mode=raw-numbers", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=ordered, unsupported",
+                vec!["--mode=ordered", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=unique-keys, unsupported",
+                vec!["--mode=unique-keys", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=recover, unsupported",
+                vec!["--mode=recover", valid],
+                Exit::Usage,
+            ),
+            (
+                "--mode=no-recovery, unsupported",
+                vec!["--mode=no-recovery", valid],
+                Exit::Usage,
+            ),
+        ];
+        // `null` doesn't parse
+        if app != "null-app" {
+            cases.push(("invalid", vec![invalid], Exit::Parse));
+            cases.push((
+                "invalid, --errors=json",
+                vec!["--errors=json", invalid],
+                Exit::Parse,
+            ));
+            cases.push(("trailing input", vec![trailing], Exit::Parse));
+            let parser = [conformance::REFERENCE]
+                .into_iter()
+                .chain(conformance::PARSERS.iter().copied())
+                .find(|parser| format!("{}-app", parser.name()) == *app)
+                .unwrap();
+            for (doc, path, src) in &crashed {
+                // Some parsers accept `late_trailing_comma.json`, so the binary only has to agree
+                // with its library
+                let expected = match conformance::run(parser, src) {
+                    Outcome::Accepted(_) => Exit::Success,
+                    _ => Exit::Parse,
+                };
+                cases.push((doc, vec![path], expected));
+                cases.push((doc, vec!["--errors=json", path], expected));
+            }
+        }
+        for (case, args, expected) in cases {
+            let actual = exit_code(&bin, &args);
+            if actual != Some(expected.code()) {
+                failures.push(format!(
+                    "{app} ({case}): expected {expected:?}, got {actual:?}"
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,98 @@
+//! Compare every parser's output with `serde_json` on documents from `json-gen`
+//!
+//! Each profile stresses one feature so a divergence points at its cause. Known divergences are
+//! recorded in `snapshots/generated.txt`; run with `SNAPSHOTS=overwrite` to accept a change in
+//! behavior.
+
+use std::fmt::Write as _;
+
+use conformance::{Outcome, PARSERS, REFERENCE};
+use json_gen::{Config, Numbers};
+
+const SEEDS: u64 = 16;
+
+fn profiles() -> Vec<(&'static str, Config)> {
+    let plain = Config {
+        size: 2 * 1024,
+        depth: 4,
+        escape_ratio: 0.0,
+        unicode_ratio: 0.0,
+        numbers: Numbers {
+            negative: false,
+            fraction: false,
+            exponent: false,
+        },
+        ..Config::default()
+    };
+    vec![
+        ("plain", plain.clone()),
+        (
+            "numbers",
+            Config {
+                numbers: Numbers::default(),
+                ..plain.clone()
+            },
+        ),
+        (
+            "escapes",
+            Config {
+                escape_ratio: 0.2,
+                ..plain.clone()
+            },
+        ),
+        (
+            "unicode",
+            Config {
+                unicode_ratio: 0.5,
+                ..plain.clone()
+            },
+        ),
+        (
+            "nested",
+            Config {
+                depth: 32,
+                min_breadth: 1,
+                breadth: 2,
+                ..plain
+            },
+        ),
+    ]
+}
+
+#[test]
+fn generated() {
+    let profiles = profiles();
+
+    let mut report = String::new();
+    for parser in PARSERS {
+        writeln!(report, "{}:", parser.name()).unwrap();
+        for (profile, config) in &profiles {
+            let mut first = None;
+            let mut mismatches = 0;
+            for seed in 0..SEEDS {
+                let src = json_gen::generate(&Config {
+                    seed,
+                    ..config.clone()
+                });
+                let Outcome::Accepted(expected) = conformance::run(REFERENCE, &src) else {
+                    panic!("{profile} seed {seed}: rejected by {}", REFERENCE.name());
+                };
+                let mismatch =
+                    conformance::diff_outcome(&expected, &conformance::run(*parser, &src));
+                if let Some(mismatch) = mismatch {
+                    mismatches += 1;
+                    first.get_or_insert(format!("seed {seed}: {mismatch}"));
+                }
+            }
+
+            match first {
+                None => writeln!(report, "  {profile}: ok").unwrap(),
+                Some(first) => {
+                    writeln!(report, "  {profile}: {mismatches}/{SEEDS} differ, {first}").unwrap()
+                }
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/generated.txt"].raw());
+}
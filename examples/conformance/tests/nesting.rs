@@ -0,0 +1,91 @@
+//! Run every `<name>-app` binary on deeply nested documents
+//!
+//! Each document is arrays nested inside an object. Whether each app succeeds, rejects the
+//! document, crashes (most likely by overflowing its stack) or runs past [`TIMEOUT`] is recorded
+//! in `snapshots/nesting.md`; run with `SNAPSHOTS=overwrite` to accept a change in behavior.
+//!
+//! The binaries are built in release, as they are benchmarked: stack use differs between
+//! profiles, and debug builds spend most of their time printing the tree.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Nesting depths to try, counting the outer object: `json_model::MAX_DEPTH` and one past it, then
+/// far past it
+const DEPTHS: &[usize] = &[128, 129, 1_000, 100_000];
+
+/// How long an app gets per document before it is killed
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `depth` levels of nesting: an object holding `depth - 1` arrays
+fn nested(depth: usize) -> String {
+    let arrays = depth - 1;
+    format!(r#"{{"a": {}{}}}"#, "[".repeat(arrays), "]".repeat(arrays))
+}
+
+fn outcome(bin: &Path, path: &Path) -> String {
+    let mut child = Command::new(bin)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if deadline < Instant::now() {
+            child.kill().unwrap();
+            child.wait().unwrap();
+            return "timeout".to_owned();
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    match status.code() {
+        Some(0) => "ok".to_owned(),
+        Some(1) => "error".to_owned(),
+        Some(code) => format!("exit {code}"),
+        None => "crash".to_owned(),
+    }
+}
+
+#[test]
+fn nesting() {
+    let apps = conformance::apps();
+    let bin_dir = conformance::build_apps(&apps, true);
+    let docs = DEPTHS
+        .iter()
+        .map(|depth| {
+            let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("nested-{depth}.json"));
+            std::fs::write(&path, nested(*depth)).unwrap();
+            path
+        })
+        .collect::<Vec<_>>();
+
+    let mut report = String::new();
+    writeln!(report, "| app | {} |", join(DEPTHS)).unwrap();
+    writeln!(report, "|---|{}", "---|".repeat(DEPTHS.len())).unwrap();
+    for app in &apps {
+        let bin = bin_dir.join(format!("{app}{}", std::env::consts::EXE_SUFFIX));
+        let outcomes = docs
+            .iter()
+            .map(|path| outcome(&bin, path))
+            .collect::<Vec<_>>();
+        writeln!(report, "| {app} | {} |", join(&outcomes)).unwrap();
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/nesting.md"].raw());
+}
+
+fn join(cells: &[impl ToString]) -> String {
+    cells
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
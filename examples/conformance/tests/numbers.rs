@@ -0,0 +1,107 @@
+//! Compare every parser's `f64`s with `serde_json`'s, bit for bit, on the numbers in `numbers.txt`
+//!
+//! Bits rather than `==` so that `-0` and `0` are told apart. Known divergences are recorded in
+//! `snapshots/numbers.txt`; run with `SNAPSHOTS=overwrite` to accept a change in behavior.
+//!
+//! Parsers with a raw number mode must instead return each number's text untouched.
+
+use std::fmt::Write as _;
+
+use conformance::{Outcome, PARSERS, RAW_NUMBERS, REFERENCE};
+use json_model::{JsonParser, RawNumberValue, Value};
+
+/// What `parser` made of `number` as the value of an object member
+fn parse(parser: &dyn JsonParser, number: &str) -> Result<f64, String> {
+    match conformance::run(parser, &format!(r#"{{"n": {number}}}"#)) {
+        Outcome::Accepted(Value::Object(mut members)) => match members.remove("n") {
+            Some(Value::Number(n)) => Ok(n),
+            other => Err(format!("accepted as {other:?}")),
+        },
+        Outcome::Accepted(other) => Err(format!("accepted as {other:?}")),
+        Outcome::Rejected(_) => Err("rejected".to_owned()),
+        Outcome::Panicked(msg) => Err(format!("panicked: {}", conformance::summarize(&msg))),
+    }
+}
+
+/// `Debug` prints the shortest representation that round-trips, so it is as precise as the bits
+fn describe(result: &Result<f64, String>) -> String {
+    match result {
+        Ok(n) => format!("{n:?}"),
+        Err(err) => err.clone(),
+    }
+}
+
+#[test]
+fn numbers() {
+    let numbers = conformance::numbers();
+    let expected = numbers
+        .iter()
+        .map(|number| parse(REFERENCE, number))
+        .collect::<Vec<_>>();
+
+    let mut report = String::new();
+    for parser in PARSERS {
+        let mut mismatches = Vec::new();
+        for (number, expected) in numbers.iter().zip(&expected) {
+            let actual = parse(*parser, number);
+            let same = match (expected, &actual) {
+                (Ok(expected), Ok(actual)) => expected.to_bits() == actual.to_bits(),
+                (Err(_), Err(_)) => true,
+                _ => false,
+            };
+            if !same {
+                mismatches.push(format!(
+                    "{}: expected {}, got {}",
+                    conformance::summarize(number),
+                    describe(expected),
+                    describe(&actual)
+                ));
+            }
+        }
+
+        if mismatches.is_empty() {
+            writeln!(report, "{}: ok", parser.name()).unwrap();
+        } else {
+            writeln!(report, "{}: {} mismatches", parser.name(), mismatches.len()).unwrap();
+            for mismatch in mismatches {
+                writeln!(report, "  {mismatch}").unwrap();
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/numbers.txt"].raw());
+}
+
+#[test]
+fn raw_numbers() {
+    let numbers = conformance::numbers();
+    let corpus = conformance::corpus("corpus");
+
+    let mut failures = Vec::new();
+    for parser in RAW_NUMBERS {
+        for number in &numbers {
+            let src = format!(r#"{{"n": {number}}}"#);
+            let raw = match parser.parse_raw_numbers(&src) {
+                Ok(RawNumberValue::Object(mut members)) => members.remove("n"),
+                _ => None,
+            };
+            if raw != Some(RawNumberValue::Number(number)) {
+                failures.push(format!("{} {number}: got {raw:?}", parser.name()));
+            }
+        }
+        // Rounding the raw numbers has to give what the plain mode builds
+        for (name, src) in &corpus {
+            let plain = conformance::run(*parser, src);
+            let raw = conformance::run_raw_numbers(*parser, src);
+            let same = match (&plain, &raw) {
+                (Outcome::Accepted(plain), Outcome::Accepted(raw)) => plain == raw,
+                (Outcome::Rejected(_), Outcome::Rejected(_)) => true,
+                _ => false,
+            };
+            if !same {
+                failures.push(format!("{} {name}: {plain:?} != {raw:?}", parser.name()));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
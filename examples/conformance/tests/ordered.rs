@@ -0,0 +1,140 @@
+//! Check every [`OrderedParser`](json_model::OrderedParser) against its plain mode and `serde_json`
+//!
+//! Keeping duplicate keys, each parser must accept and reject what its plain mode does, build the
+//! same value once the last of each key wins, and list members in the order `serde_json` does.
+//! Rejecting them, it must reject exactly the documents in which it found a repeated key. Problems
+//! are recorded in `snapshots/ordered.txt`; run with `SNAPSHOTS=overwrite` to accept a change in
+//! behavior.
+
+use std::fmt::Write as _;
+
+use conformance::{Outcome, ORDERED};
+use json_model::{DuplicateKeys, OrderedParser, OrderedValue};
+
+/// Documents with and without repeated keys, besides the corpus's
+const KEYS: &[(&str, &str)] = &[
+    ("repeated", r#"{"a": 1, "a": 2}"#),
+    ("repeated_apart", r#"{"a": 1, "b": 2, "a": 3}"#),
+    ("repeated_in_array", r#"{"l": [{"a": null, "a": null}]}"#),
+    ("repeated_in_object", r#"{"o": {"k": true, "k": false}}"#),
+    ("repeated_escaped", r#"{"a": 1, "\u0061": 2}"#),
+    ("distinct_case", r#"{"a": 1, "A": 2}"#),
+    ("distinct_normalization", "{\"\u{e9}\": 1, \"e\u{301}\": 2}"),
+    (
+        "order",
+        r#"{"z": 1, "y": {"b": 2, "a": 3}, "x": [{"d": 4, "c": 5}]}"#,
+    ),
+];
+
+/// Every key in `value`, depth first and in document order
+fn keys(value: &OrderedValue, out: &mut Vec<String>) {
+    match value {
+        OrderedValue::Array(items) => items.iter().for_each(|item| keys(item, out)),
+        OrderedValue::Object(members) => {
+            for (key, member) in members {
+                out.push(key.clone());
+                keys(member, out);
+            }
+        }
+        OrderedValue::Null
+        | OrderedValue::Bool(_)
+        | OrderedValue::Number(_)
+        | OrderedValue::String(_) => (),
+    }
+}
+
+/// Whether any object in `value` has the same key twice
+fn repeats(value: &OrderedValue) -> bool {
+    match value {
+        OrderedValue::Array(items) => items.iter().any(repeats),
+        OrderedValue::Object(members) => {
+            DuplicateKeys::Reject.check(members).is_err()
+                || members.iter().any(|(_, member)| repeats(member))
+        }
+        OrderedValue::Null
+        | OrderedValue::Bool(_)
+        | OrderedValue::Number(_)
+        | OrderedValue::String(_) => false,
+    }
+}
+
+/// The keys of `src` as `parser` keeps them, if it accepts it
+fn key_order(parser: &dyn OrderedParser, src: &str) -> Option<(Vec<String>, bool)> {
+    let value = parser.parse_ordered(src, DuplicateKeys::Keep).ok()?;
+    let mut order = Vec::new();
+    keys(&value, &mut order);
+    Some((order, repeats(&value)))
+}
+
+/// What went wrong with `parser` on `src`, if anything
+fn check(parser: &dyn OrderedParser, src: &str) -> Option<String> {
+    let plain = conformance::run(parser, src);
+    let kept = conformance::run_ordered(parser, src, DuplicateKeys::Keep);
+    let unique = conformance::run_ordered(parser, src, DuplicateKeys::Reject);
+    conformance::compare_modes("ordered", &plain, &kept, |plain| {
+        // Only parse again for the keys once the values agree
+        let (order, repeated) = key_order(parser, src).unwrap();
+        if let Some((expected, _)) = key_order(&serde_json_app::SerdeJson, src) {
+            if order != expected {
+                return Some(format!("keys in order {order:?}, expected {expected:?}"));
+            }
+        }
+        match (repeated, &unique) {
+            (true, Outcome::Rejected(_)) => None,
+            (false, Outcome::Accepted(unique)) => conformance::diff(plain, unique)
+                .map(|diff| format!("unique-keys value differs, {diff}")),
+            (true, unique) => Some(format!("repeated keys, unique-keys {}", unique.verb())),
+            (false, unique) => Some(format!("no repeated keys, unique-keys {}", unique.verb())),
+        }
+    })
+    // Whatever the ordered mode rejects, the unique-keys mode has to as well
+    .or_else(|| match (&kept, &unique) {
+        (Outcome::Rejected(_) | Outcome::Panicked(_), Outcome::Accepted(_)) => Some(format!(
+            "ordered mode {}, unique-keys accepted",
+            kept.verb()
+        )),
+        _ => None,
+    })
+}
+
+#[test]
+fn ordered() {
+    let corpus = conformance::corpus("corpus");
+    let malformed = conformance::malformed();
+    let docs = KEYS
+        .iter()
+        .map(|(name, src)| (*name, *src))
+        .chain(
+            corpus
+                .iter()
+                .map(|(name, src)| (name.as_str(), src.as_str())),
+        )
+        .chain(
+            malformed
+                .iter()
+                .map(|case| (case.name.as_str(), case.src.as_str())),
+        )
+        .collect::<Vec<_>>();
+
+    let mut report = String::new();
+    let mut parsers = ORDERED.to_vec();
+    parsers.sort_by_key(|parser| parser.name());
+    for parser in parsers {
+        let problems = docs
+            .iter()
+            .filter_map(|(name, src)| {
+                check(parser, src).map(|problem| format!("{name}: {problem}"))
+            })
+            .collect::<Vec<_>>();
+        if problems.is_empty() {
+            writeln!(report, "{}: ok", parser.name()).unwrap();
+        } else {
+            writeln!(report, "{}: {} problems", parser.name(), problems.len()).unwrap();
+            for problem in problems {
+                writeln!(report, "  {problem}").unwrap();
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/ordered.txt"].raw());
+}
@@ -0,0 +1,143 @@
+//! Check the spans of every [`SpannedParser`](json_model::SpannedParser) against the source
+//!
+//! Each parser's spanned mode must accept and reject what its plain mode does, build the same
+//! value once spans are dropped, and give every value and key a span whose text spells it.
+//! Problems are recorded in `snapshots/spanned.txt`; run with `SNAPSHOTS=overwrite` to accept a
+//! change in behavior.
+
+use std::fmt::Write as _;
+
+use conformance::SPANNED;
+use json_model::{Span, Spanned, SpannedValue};
+
+/// Describe the first span in `value` that isn't inside `parent` and after `after`, or whose
+/// text doesn't spell its value
+fn check(
+    src: &str,
+    path: &str,
+    parent: &Span,
+    after: usize,
+    value: &Spanned<SpannedValue>,
+) -> Option<String> {
+    let span = &value.span;
+    let Some(text) = src.get(span.clone()) else {
+        return Some(format!("{path}: span {span:?} is not in the input"));
+    };
+    if span.start < parent.start.max(after) || parent.end < span.end {
+        return Some(format!(
+            "{path}: span {span:?} is outside {parent:?} or overlaps its sibling"
+        ));
+    }
+    let delimited =
+        |open, close| text.len() >= 2 && text.starts_with(open) && text.ends_with(close);
+    let spelled = match &value.value {
+        SpannedValue::Null => text == "null",
+        SpannedValue::Bool(b) => text == b.to_string(),
+        SpannedValue::Number(n) => text.parse::<f64>() == Ok(*n),
+        SpannedValue::String(_) => delimited('"', '"'),
+        SpannedValue::Array(_) => delimited('[', ']'),
+        SpannedValue::Object(_) => delimited('{', '}'),
+    };
+    if !spelled {
+        return Some(format!(
+            "{path}: span {span:?} is {}, not {}",
+            conformance::summarize(&format!("{text:?}")),
+            kind(&value.value)
+        ));
+    }
+    match &value.value {
+        SpannedValue::Array(items) => {
+            let mut after = span.start;
+            for (i, item) in items.iter().enumerate() {
+                let problem = check(src, &format!("{path}[{i}]"), span, after, item);
+                if problem.is_some() {
+                    return problem;
+                }
+                after = item.span.end;
+            }
+            None
+        }
+        SpannedValue::Object(members) => {
+            let mut after = span.start;
+            for (key, member) in members {
+                let path = format!("{path}[{:?}]", key.value);
+                let key = Spanned::new(SpannedValue::String(key.value.clone()), key.span.clone());
+                let problem = check(src, &format!("{path} key"), span, after, &key)
+                    .or_else(|| check(src, &path, span, key.span.end, member));
+                if problem.is_some() {
+                    return problem;
+                }
+                after = member.span.end;
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn kind(value: &SpannedValue) -> String {
+    match value {
+        SpannedValue::Null => "null".to_owned(),
+        SpannedValue::Bool(b) => b.to_string(),
+        SpannedValue::Number(n) => format!("number {n}"),
+        SpannedValue::String(_) => "a string".to_owned(),
+        SpannedValue::Array(_) => "an array".to_owned(),
+        SpannedValue::Object(_) => "an object".to_owned(),
+    }
+}
+
+#[test]
+fn spanned() {
+    let corpus = conformance::corpus("corpus");
+    let malformed = conformance::malformed();
+    let docs = corpus
+        .iter()
+        .map(|(name, src)| (name.as_str(), src.as_str()))
+        .chain(
+            malformed
+                .iter()
+                .map(|case| (case.name.as_str(), case.src.as_str())),
+        );
+
+    let mut report = String::new();
+    let mut parsers = SPANNED.to_vec();
+    parsers.sort_by_key(|parser| parser.name());
+    for parser in parsers {
+        let mut problems = Vec::new();
+        for (name, src) in docs.clone() {
+            let problem = conformance::compare_modes(
+                "spanned",
+                &conformance::run(parser, src),
+                &conformance::run_spanned(parser, src),
+                |_| {
+                    // Only parse again for the spans once the values agree
+                    let value = parser.parse_spanned(src).unwrap();
+                    let trimmed = src.trim_start();
+                    let root = src.len() - trimmed.len()..src.trim_end().len();
+                    if value.span != root {
+                        Some(format!(
+                            "$: span {:?} is not the document, {root:?}",
+                            value.span
+                        ))
+                    } else {
+                        check(src, "$", &root, 0, &value)
+                    }
+                },
+            );
+            if let Some(problem) = problem {
+                problems.push(format!("{name}: {problem}"));
+            }
+        }
+
+        if problems.is_empty() {
+            writeln!(report, "{}: ok", parser.name()).unwrap();
+        } else {
+            writeln!(report, "{}: {} problems", parser.name(), problems.len()).unwrap();
+            for problem in problems {
+                writeln!(report, "  {problem}").unwrap();
+            }
+        }
+    }
+
+    snapbox::assert_data_eq!(report, snapbox::file!["snapshots/spanned.txt"].raw());
+}
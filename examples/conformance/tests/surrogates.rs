@@ -0,0 +1,63 @@
+//! Every parser, in every mode it has, combines UTF-16 surrogate pairs and rejects lone surrogates
+//!
+//! A lone surrogate can't be stored in a Rust string, so rejecting the document is the only
+//! policy that neither loses nor invents data; it is also what `serde_json` does. Each case is
+//! tried as an object's key and as its value.
+
+use conformance::Outcome;
+use json_model::{Map, Value};
+
+/// Escaped strings and what they decode to, or `None` if they must be rejected
+const CASES: &[(&str, Option<&str>)] = &[
+    (r"\uD83D\uDE10", Some("\u{1F610}")),
+    (r"\ud834\udd1e", Some("\u{1D11E}")),
+    (r"\uD800\uDC00", Some("\u{10000}")),
+    (r"\uDBFF\uDFFF", Some("\u{10FFFF}")),
+    (r"a\uD83D\uDE10b", Some("a\u{1F610}b")),
+    (r"\uD83D\uDE10\uD83D\uDE10", Some("\u{1F610}\u{1F610}")),
+    // The code points on either side of the surrogates
+    (r"\uD7FF\uE000", Some("\u{D7FF}\u{E000}")),
+    (r"\uD800", None),
+    (r"\uDBFF", None),
+    (r"\uDC00", None),
+    (r"\uDFFF", None),
+    (r"\uD800x", None),
+    (r"\uD800\n", None),
+    (r"\uD800\u0041", None),
+    (r"\uD800\uD800", None),
+    (r"\uDE10\uD83D", None),
+    (r"x\uDC00\uDC00", None),
+];
+
+#[test]
+fn surrogates() {
+    let mut failures = Vec::new();
+    for &(escaped, expected) in CASES {
+        for (src, expected) in [
+            (
+                format!(r#"{{"{escaped}": null}}"#),
+                expected.map(|s| object(s, Value::Null)),
+            ),
+            (
+                format!(r#"{{"k": "{escaped}"}}"#),
+                expected.map(|s| object("k", Value::String(s.to_owned()))),
+            ),
+        ] {
+            for (name, mode, outcome) in conformance::every_mode(&src) {
+                let ok = match (&outcome, &expected) {
+                    (Outcome::Accepted(actual), Some(expected)) => actual == expected,
+                    (Outcome::Rejected(_), None) => true,
+                    _ => false,
+                };
+                if !ok {
+                    failures.push(format!("{name} ({mode}) {src}: {outcome:?}"));
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn object(key: &str, value: Value) -> Value {
+    Value::Object(Map::from([(key.to_owned(), value)]))
+}
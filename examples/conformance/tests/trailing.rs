@@ -0,0 +1,53 @@
+//! Every parser, in every mode it has, consumes its whole input
+//!
+//! Whitespace may surround the document but nothing else may follow it: a parser that stops after
+//! the first value does less work than the others, so benchmarks wouldn't compare like with like.
+
+use conformance::Outcome;
+use json_model::{Map, Value};
+
+/// Documents that must be rejected for what follows the object
+const TRAILING: &[&str] = &[
+    r#"{"a": 1} garbage"#,
+    r#"{"a": 1}x"#,
+    r#"{"a": 1}}"#,
+    r#"{"a": 1}]"#,
+    r#"{"a": 1},"#,
+    r#"{"a": 1} {"a": 1}"#,
+    r#"{"a": 1} null"#,
+    r#"{"a": 1} 1"#,
+    r#"{"a": 1} "a""#,
+    "{\"a\": 1}\n\n/",
+    "{\"a\": 1}\0",
+];
+
+/// Documents that must be accepted despite the whitespace around the object
+const WHITESPACE: &[&str] = &[
+    r#"{"a": 1} "#,
+    "{\"a\": 1}\n",
+    "{\"a\": 1} \t\r\n",
+    " \n{\"a\": 1}",
+];
+
+#[test]
+fn trailing() {
+    let expected = Value::Object(Map::from([("a".to_owned(), Value::Number(1.0))]));
+    let mut failures = Vec::new();
+    let docs = TRAILING
+        .iter()
+        .map(|src| (src, None))
+        .chain(WHITESPACE.iter().map(|src| (src, Some(&expected))));
+    for (src, expected) in docs {
+        for (name, mode, outcome) in conformance::every_mode(src) {
+            let ok = match (&outcome, expected) {
+                (Outcome::Accepted(actual), Some(expected)) => actual == expected,
+                (Outcome::Rejected(_), None) => true,
+                _ => false,
+            };
+            if !ok {
+                failures.push(format!("{name} ({mode}) {src:?}: {outcome:?}"));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,107 @@
+//! A counting `#[global_allocator]` for the example apps
+//!
+//! Apps opt in through their `count-alloc` feature, which installs [`CountingAlloc`] and calls
+//! [`report`] once the input has been parsed:
+//! ```ignore
+//! #[cfg(feature = "count-alloc")]
+//! #[global_allocator]
+//! static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
+//! ```
+//! The counts cover the whole process, including reading the input, so compare them against
+//! `null-app`'s.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Where [`report`] writes the [`Stats`], if set
+pub const REPORT_ENV: &str = "COUNT_ALLOC_REPORT";
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// [`System`], counting every allocation
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: defers to `System`, only counting on the side
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    /// Counted as freeing the old block and allocating the new one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocator activity since the process started
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of allocations, including reallocations
+    pub allocations: usize,
+    /// Total bytes requested
+    pub bytes: usize,
+    /// Most bytes live at once
+    pub peak_bytes: usize,
+}
+
+pub fn stats() -> Stats {
+    Stats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK.load(Ordering::Relaxed),
+    }
+}
+
+/// Write [`stats`] as JSON to the file named by [`REPORT_ENV`], if set
+pub fn report() {
+    // Snapshot before formatting allocates
+    let stats = stats();
+    let Some(path) = std::env::var_os(REPORT_ENV) else {
+        return;
+    };
+    let json = format!(
+        "{{\"allocations\": {}, \"bytes\": {}, \"peak_bytes\": {}}}\n",
+        stats.allocations, stats.bytes, stats.peak_bytes
+    );
+    if let Err(err) = std::fs::write(&path, json) {
+        eprintln!("{}: {err}", path.to_string_lossy());
+    }
+}
@@ -0,0 +1,19 @@
+use count_alloc::CountingAlloc;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[test]
+fn counts_allocations() {
+    let before = count_alloc::stats();
+    let buffer = std::hint::black_box(vec![0u8; 1 << 20]);
+    let during = count_alloc::stats();
+    drop(buffer);
+    let after = count_alloc::stats();
+
+    assert!(during.allocations > before.allocations);
+    assert!(during.bytes >= before.bytes + (1 << 20));
+    assert!(during.peak_bytes >= 1 << 20);
+    // Freeing doesn't lower the high-water mark
+    assert!(after.peak_bytes >= during.peak_bytes);
+}
@@ -1,29 +1,130 @@
-use lrlex::lrlex_mod;
-use lrpar::lrpar_mod;
-use std::{env, fs};
+use grmtools_app::{json_l, json_y};
+use json_model::cli::{self, Args, ErrorFormat, Exit};
 
-lrlex_mod!("json.l");
-lrpar_mod!("json.y");
-
-mod json_val;
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+        #[cfg(feature = "recover")]
+        cli::Mode::Recover,
+        #[cfg(feature = "recover")]
+        cli::Mode::NoRecovery,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| grmtools_app::Grmtools.parse_spanned(src));
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            grmtools_app::Grmtools.parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            grmtools_app::Grmtools.parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            grmtools_app::Grmtools.parse_ordered(src, keys)
+        });
+    }
+
+    #[cfg(feature = "recover")]
+    if let Some(recovery) = match args.mode {
+        cli::Mode::Recover => Some(grmtools_app::recover::Recovery::CpctPlus),
+        cli::Mode::NoRecovery => Some(grmtools_app::recover::Recovery::None),
+        _ => None,
+    } {
+        recover(&args, &src, recovery);
+    }
 
     let lexerdef = json_l::lexerdef();
     let lexer = lexerdef.lexer(&src);
     let (res, errs) = json_y::parse(&lexer);
-    for e in errs {
-        println!("{}", e.pp(&lexer, &json_y::token_epp));
+    for e in &errs {
+        match args.errors {
+            ErrorFormat::Text => eprintln!("{}", e.pp(&lexer, &json_y::token_epp)),
+            ErrorFormat::Json => cli::print_report(&src, &grmtools_app::parse_error(&lexer, e)),
+        }
+    }
+    // Error recovery may still produce a value, but the document was invalid
+    if !errs.is_empty() {
+        Exit::Parse.exit();
     }
     match res {
-        Some(r) => {
+        Some(Ok(r)) => {
             #[cfg(debug_assertions)]
             println!("{r:#?}");
             #[cfg(not(debug_assertions))]
             let _ = std::hint::black_box(r);
         }
-        None => panic!(),
+        // A string's escapes are only checked once it is decoded
+        Some(Err(e)) => {
+            let err = grmtools_app::action_error(e);
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{err}"),
+                ErrorFormat::Json => cli::print_report(&src, &err),
+            }
+            Exit::Parse.exit();
+        }
+        // Recovery gives up with errors, handled above, so this shouldn't happen; report it as
+        // `Grmtools::parse` does rather than crash
+        None => {
+            let err = json_model::ParseError::new("no parse result");
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{err}"),
+                ErrorFormat::Json => cli::print_report(&src, &err),
+            }
+            Exit::Parse.exit();
+        }
+    }
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
+}
+
+/// Parse with `recovery`, printing every error and, in debug builds, the repairs and the value
+#[cfg(feature = "recover")]
+fn recover(args: &Args, src: &str, recovery: grmtools_app::recover::Recovery) -> ! {
+    let recovered = grmtools_app::recover::parse(src, recovery);
+    for err in &recovered.errors {
+        match args.errors {
+            ErrorFormat::Text => eprintln!("{}", err.error),
+            ErrorFormat::Json => cli::print_report(src, &err.error),
+        }
+    }
+    #[cfg(debug_assertions)]
+    println!("{recovered:#?}");
+    #[cfg(not(debug_assertions))]
+    std::hint::black_box(&recovered);
+    if recovered.errors.is_empty() {
+        Exit::Success.exit()
+    } else {
+        Exit::Parse.exit()
     }
 }
@@ -0,0 +1,69 @@
+%start Member
+%expect-unused Unmatched "UNMATCHED"
+
+%%
+
+Object -> Result<BorrowedValue<'input>, Box<dyn Error>>:
+    "{" ObjectMembersOpt "}" { Ok(BorrowedValue::Object(BorrowedMap::from_iter($2?))) }
+  ;
+
+ObjectMembersOpt -> Result<Vec<BorrowedMember<'input>>, Box<dyn Error>>:
+    ObjectMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ObjectMembers -> Result<Vec<BorrowedMember<'input>>, Box<dyn Error>>:
+    ObjectMembers "," ObjectMember { flatten($1, $3) }
+  | ObjectMember { Ok(vec![$1?]) }
+  ;
+
+ObjectMember -> Result<BorrowedMember<'input>, Box<dyn Error>>:
+    "STRING" ":" Member {
+      let span = $1?.span();
+      Ok((crate::string(span, $lexer.span_str(span))?, $3?))
+    }
+  ;
+
+Member -> Result<BorrowedValue<'input>, Box<dyn Error>>:
+    "[" ArrayMembersOpt "]" { Ok(BorrowedValue::Array($2?)) }
+  | "FALSE" { Ok(BorrowedValue::Bool(false)) }
+  | "FLOAT" { Ok(BorrowedValue::Number($lexer.span_str($1?.span()).parse::<f64>().unwrap())) }
+  | "NULL" { Ok(BorrowedValue::Null) }
+  | Object { $1 }
+  | "STRING" {
+      let span = $1?.span();
+      Ok(BorrowedValue::String(crate::string(span, $lexer.span_str(span))?))
+    }
+  | "TRUE" { Ok(BorrowedValue::Bool(true)) }
+  ;
+
+ArrayMembersOpt -> Result<Vec<BorrowedValue<'input>>, Box<dyn Error>>:
+    ArrayMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ArrayMembers -> Result<Vec<BorrowedValue<'input>>, Box<dyn Error>>:
+    ArrayMembers "," Member { flatten($1, $3) }
+  | Member { Ok(vec![$1?])}
+  ;
+
+Unmatched -> ():
+  "UNMATCHED" { }
+  ;
+
+%%
+
+use json_model::{BorrowedMap, BorrowedValue};
+use std::borrow::Cow;
+use std::error::Error;
+
+type BorrowedMember<'input> = (Cow<'input, str>, BorrowedValue<'input>);
+
+fn flatten<T>(lhs: Result<Vec<T>, Box<dyn Error>>, rhs: Result<T, Box<dyn Error>>)
+  -> Result<Vec<T>, Box<dyn Error>>
+{
+    let mut lhs = lhs?;
+    let rhs = rhs?;
+    lhs.push(rhs);
+    Ok(lhs)
+}
@@ -1,26 +1,70 @@
 use cfgrammar::yacc::YaccKind;
 use lrlex::CTLexerBuilder;
+use lrpar::RecoveryKind;
 use std::{env, path::PathBuf};
 
 fn main() {
+    build("json", "json_l", "json_y");
+    // Other modes are only generated on request, so they don't add to the build being benchmarked
+    if env::var_os("CARGO_FEATURE_SPANNED").is_some() {
+        build("spanned", "spanned_l", "spanned_y");
+    }
+    if env::var_os("CARGO_FEATURE_BORROWED").is_some() {
+        build("borrowed", "borrowed_l", "borrowed_y");
+    }
+    if env::var_os("CARGO_FEATURE_RAW_NUMBERS").is_some() {
+        build("raw_numbers", "raw_numbers_l", "raw_numbers_y");
+    }
+    if env::var_os("CARGO_FEATURE_ORDERED").is_some() {
+        build("ordered", "ordered_l", "ordered_y");
+    }
+    if env::var_os("CARGO_FEATURE_RECOVER").is_some() {
+        // `json.y` again, stopping at the first syntax error instead of repairing it
+        build_with(
+            "json",
+            "no_recovery",
+            RecoveryKind::None,
+            "no_recovery_l",
+            "no_recovery_y",
+        );
+    }
+}
+
+/// Generate a lexer from `json.l` and a parser from `<name>.y`, repairing syntax errors with CPCT+
+fn build(name: &str, lexer_mod: &'static str, parser_mod: &'static str) {
+    build_with(name, name, RecoveryKind::CPCTPlus, lexer_mod, parser_mod);
+}
+
+/// Generate `<out>.l.rs` from `json.l` and `<out>.y.rs` from `<grammar>.y`
+fn build_with(
+    grammar: &str,
+    out: &str,
+    recoverer: RecoveryKind,
+    lexer_mod: &'static str,
+    parser_mod: &'static str,
+) {
+    let out_dir = env::var("OUT_DIR").unwrap();
     CTLexerBuilder::new()
         .lrpar_config(|ctp| {
             ctp.yacckind(YaccKind::Grmtools)
-                .grammar_path("json.y")
+                .grammar_path(format!("{grammar}.y"))
+                .recoverer(recoverer)
                 .output_path(
-                    [env::var("OUT_DIR").unwrap().as_str(), "json.y.rs"]
+                    [out_dir.as_str(), &format!("{out}.y.rs")]
                         .iter()
                         .collect::<PathBuf>(),
                 )
-                .mod_name("json_y")
+                .mod_name(parser_mod)
+                .visibility(lrpar::Visibility::Public)
         })
         .lexer_path("json.l")
         .output_path(
-            [env::var("OUT_DIR").unwrap().as_str(), "json.l.rs"]
+            [out_dir.as_str(), &format!("{out}.l.rs")]
                 .iter()
                 .collect::<PathBuf>(),
         )
-        .mod_name("json_l")
+        .mod_name(lexer_mod)
+        .visibility(lrlex::Visibility::Public)
         .build()
         .unwrap();
 }
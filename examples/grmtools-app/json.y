@@ -1,10 +1,10 @@
-%start Object
+%start Member
 %expect-unused Unmatched "UNMATCHED"
 
 %%
 
 Object -> Result<Value, Box<dyn Error>>:
-    "{" ObjectMembersOpt "}" { Ok(Value::Object(HashMap::from_iter($2?))) }
+    "{" ObjectMembersOpt "}" { Ok(Value::Object(Map::from_iter($2?))) }
   ;
 
 ObjectMembersOpt -> Result<Vec<(String, Value)>, Box<dyn Error>>:
@@ -19,22 +19,22 @@ ObjectMembers -> Result<Vec<(String, Value)>, Box<dyn Error>>:
 
 ObjectMember -> Result<(String, Value), Box<dyn Error>>:
     "STRING" ":" Member {
-      let s = $lexer.span_str($1.unwrap().span());
-      Ok((s[1..s.len() - 1].to_owned(), $3?))
+      let span = $1?.span();
+      Ok((crate::string(span, $lexer.span_str(span))?.into_owned(), $3?))
     }
   ;
 
 Member -> Result<Value, Box<dyn Error>>:
     "[" ArrayMembersOpt "]" { Ok(Value::Array($2?)) }
-  | "FALSE" { Ok(Value::Boolean(false)) }
-  | "FLOAT" { Ok(Value::Num($lexer.span_str($1?.span()).parse::<f64>().unwrap())) }
+  | "FALSE" { Ok(Value::Bool(false)) }
+  | "FLOAT" { Ok(Value::Number($lexer.span_str($1?.span()).parse::<f64>().unwrap())) }
   | "NULL" { Ok(Value::Null) }
   | Object { $1 }
   | "STRING" {
-      let s = $lexer.span_str($1.unwrap().span());
-      Ok(Value::Str(s[1..s.len() - 1].to_owned()))
+      let span = $1?.span();
+      Ok(Value::String(crate::string(span, $lexer.span_str(span))?.into_owned()))
     }
-  | "TRUE" { Ok(Value::Boolean(true)) }
+  | "TRUE" { Ok(Value::Bool(true)) }
   ;
 
 ArrayMembersOpt -> Result<Vec<Value>, Box<dyn Error>>:
@@ -53,8 +53,8 @@ Unmatched -> ():
 
 %%
 
-use crate::json_val::Value;
-use std::{collections::HashMap, error::Error};
+use json_model::{Map, Value};
+use std::error::Error;
 
 fn flatten<T>(lhs: Result<Vec<T>, Box<dyn Error>>, rhs: Result<T, Box<dyn Error>>)
   -> Result<Vec<T>, Box<dyn Error>>
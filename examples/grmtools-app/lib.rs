@@ -0,0 +1,238 @@
+#[cfg(feature = "recover")]
+pub mod recover;
+
+use std::borrow::Cow;
+use std::error::Error;
+
+use json_model::escape::unescape;
+use json_model::{JsonParser, ParseError, Value};
+use lrlex::{lrlex_mod, DefaultLexerTypes, LRNonStreamingLexer};
+use lrpar::lrpar_mod;
+use lrpar::{LexError as _, LexParseError, Lexeme as _, ParseRepair};
+
+lrlex_mod!("json.l");
+lrpar_mod!("json.y");
+#[cfg(feature = "spanned")]
+lrlex_mod!("spanned.l");
+#[cfg(feature = "spanned")]
+lrpar_mod!("spanned.y");
+#[cfg(feature = "borrowed")]
+lrlex_mod!("borrowed.l");
+#[cfg(feature = "borrowed")]
+lrpar_mod!("borrowed.y");
+#[cfg(feature = "raw-numbers")]
+lrlex_mod!("raw_numbers.l");
+#[cfg(feature = "raw-numbers")]
+lrpar_mod!("raw_numbers.y");
+#[cfg(feature = "ordered")]
+lrlex_mod!("ordered.l");
+#[cfg(feature = "ordered")]
+lrpar_mod!("ordered.y");
+#[cfg(feature = "recover")]
+lrlex_mod!("no_recovery.l");
+#[cfg(feature = "recover")]
+lrpar_mod!("no_recovery.y");
+
+pub struct Grmtools;
+
+impl JsonParser for Grmtools {
+    fn name(&self) -> &'static str {
+        "grmtools"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        let lexerdef = json_l::lexerdef();
+        let lexer = lexerdef.lexer(src);
+        let (res, errs) = json_y::parse(&lexer);
+        if let Some(e) = errs.first() {
+            return Err(parse_error(&lexer, e));
+        }
+        match res {
+            Some(r) => r.map_err(action_error),
+            None => Err(ParseError::new("no parse result")),
+        }
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Grmtools {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        let lexerdef = spanned_l::lexerdef();
+        let lexer = lexerdef.lexer(src);
+        let (res, errs) = spanned_y::parse(&lexer);
+        if let Some(e) = errs.first() {
+            return Err(parse_error(&lexer, e));
+        }
+        match res {
+            Some(r) => r.map_err(action_error),
+            None => Err(ParseError::new("no parse result")),
+        }
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Grmtools {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        let lexerdef = borrowed_l::lexerdef();
+        let lexer = lexerdef.lexer(src);
+        let (res, errs) = borrowed_y::parse(&lexer);
+        if let Some(e) = errs.first() {
+            return Err(parse_error(&lexer, e));
+        }
+        match res {
+            Some(r) => r.map_err(action_error),
+            None => Err(ParseError::new("no parse result")),
+        }
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Grmtools {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        let lexerdef = raw_numbers_l::lexerdef();
+        let lexer = lexerdef.lexer(src);
+        let (res, errs) = raw_numbers_y::parse(&lexer);
+        if let Some(e) = errs.first() {
+            return Err(parse_error(&lexer, e));
+        }
+        match res {
+            Some(r) => r.map_err(action_error),
+            None => Err(ParseError::new("no parse result")),
+        }
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Grmtools {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        let lexerdef = ordered_l::lexerdef();
+        let lexer = lexerdef.lexer(src);
+        let (res, errs) = ordered_y::parse(&lexer, keys);
+        if let Some(e) = errs.first() {
+            return Err(parse_error(&lexer, e));
+        }
+        match res {
+            Some(r) => r.map_err(action_error),
+            None => Err(ParseError::new("no parse result")),
+        }
+    }
+}
+
+/// Decode a `STRING` lexeme found at `span`, quotes included
+fn string(span: cfgrammar::Span, lexeme: &str) -> Result<Cow<'_, str>, Box<dyn Error>> {
+    unescape(&lexeme[1..lexeme.len() - 1])
+        .map_err(|err| err.into_parse_error(span.start() + 1).into())
+}
+
+/// The actions only fail with a [`ParseError`], from [`string`] or a duplicate key
+pub fn action_error(err: Box<dyn Error>) -> ParseError {
+    match err.downcast::<ParseError>() {
+        Ok(err) => *err,
+        Err(err) => ParseError::new(err.to_string()),
+    }
+}
+
+/// The expected set is the tokens that `lrpar`'s repair sequences would insert first
+pub fn parse_error(
+    lexer: &LRNonStreamingLexer<'_, '_, DefaultLexerTypes<u32>>,
+    err: &LexParseError<u32, DefaultLexerTypes<u32>>,
+) -> ParseError {
+    let (offset, expected) = match err {
+        LexParseError::LexError(e) => (e.span().start(), Vec::new()),
+        LexParseError::ParseError(e) => {
+            let expected = e
+                .repairs()
+                .iter()
+                .filter_map(|repair| match repair.first() {
+                    Some(ParseRepair::Insert(tidx)) => json_y::token_epp(*tidx),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            (e.lexeme().span().start(), expected)
+        }
+    };
+    ParseError::new(err.pp(lexer, &json_y::token_epp))
+        .with_offset(offset)
+        .with_expected(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use json_model::Map;
+
+    use super::*;
+
+    fn parse(src: &str) -> Result<Value, Option<usize>> {
+        Grmtools.parse(src).map_err(|err| err.offset)
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            parse(r#"{"a\"b\\c\/\b\f\n\r\t\u00e9\uD83D\uDE10": "\""}"#),
+            Ok(Value::Object(Map::from([(
+                "a\"b\\c/\x08\x0C\n\r\t\u{e9}\u{1F610}".to_owned(),
+                Value::String("\"".to_owned())
+            )])))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(parse(r#"{"ok": "\x"}"#), Err(Some(8)));
+        assert_eq!(parse(r#"{"\uDE10": 1}"#), Err(Some(2)));
+        assert_eq!(parse(r#"{"\uD83D\n": 1}"#), Err(Some(2)));
+    }
+
+    #[cfg(feature = "recover")]
+    #[test]
+    fn recovers() {
+        use recover::{Recovery, Repair};
+
+        let src = r#"{"a": [1 2], "b" 3}"#;
+        let recovered = recover::parse(src, Recovery::CpctPlus);
+        // Which of the equally cheap repairs comes first, and so shapes the value, varies
+        let Some(Value::Object(members)) = recovered.value else {
+            panic!("expected a repaired object, got {:?}", recovered.value)
+        };
+        assert_eq!(members["b"], Value::Number(3.0));
+        let errors = recovered
+            .errors
+            .iter()
+            .map(|err| {
+                let mut repairs = err.repairs.clone();
+                repairs.sort_by_key(|repair| format!("{repair:?}"));
+                (err.error.offset, repairs)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                (
+                    Some(9),
+                    vec![vec![Repair::Delete(9..10)], vec![Repair::Insert(",")]]
+                ),
+                (Some(17), vec![vec![Repair::Insert(":")]]),
+            ]
+        );
+
+        let stopped = recover::parse(src, Recovery::None);
+        assert_eq!(stopped.value, None);
+        assert_eq!(stopped.errors.len(), 1);
+        assert_eq!(stopped.errors[0].error.offset, Some(9));
+        assert!(stopped.errors[0].repairs.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+%start Member
+%parse-param keys: DuplicateKeys
+%expect-unused Unmatched "UNMATCHED"
+
+%%
+
+Object -> Result<OrderedValue, Box<dyn Error>>:
+    "{" ObjectMembersOpt "}" {
+      let members = $2?;
+      keys.check(&members)
+          .map_err(|err| ParseError::new(err.to_string()).with_offset($span.start()))?;
+      Ok(OrderedValue::Object(members))
+    }
+  ;
+
+ObjectMembersOpt -> Result<Vec<(String, OrderedValue)>, Box<dyn Error>>:
+    ObjectMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ObjectMembers -> Result<Vec<(String, OrderedValue)>, Box<dyn Error>>:
+    ObjectMembers "," ObjectMember { flatten($1, $3) }
+  | ObjectMember { Ok(vec![$1?]) }
+  ;
+
+ObjectMember -> Result<(String, OrderedValue), Box<dyn Error>>:
+    "STRING" ":" Member {
+      let span = $1?.span();
+      Ok((crate::string(span, $lexer.span_str(span))?.into_owned(), $3?))
+    }
+  ;
+
+Member -> Result<OrderedValue, Box<dyn Error>>:
+    "[" ArrayMembersOpt "]" { Ok(OrderedValue::Array($2?)) }
+  | "FALSE" { Ok(OrderedValue::Bool(false)) }
+  | "FLOAT" { Ok(OrderedValue::Number($lexer.span_str($1?.span()).parse::<f64>().unwrap())) }
+  | "NULL" { Ok(OrderedValue::Null) }
+  | Object { $1 }
+  | "STRING" {
+      let span = $1?.span();
+      Ok(OrderedValue::String(crate::string(span, $lexer.span_str(span))?.into_owned()))
+    }
+  | "TRUE" { Ok(OrderedValue::Bool(true)) }
+  ;
+
+ArrayMembersOpt -> Result<Vec<OrderedValue>, Box<dyn Error>>:
+    ArrayMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ArrayMembers -> Result<Vec<OrderedValue>, Box<dyn Error>>:
+    ArrayMembers "," Member { flatten($1, $3) }
+  | Member { Ok(vec![$1?])}
+  ;
+
+Unmatched -> ():
+  "UNMATCHED" { }
+  ;
+
+%%
+
+use json_model::{DuplicateKeys, OrderedValue, ParseError};
+use std::error::Error;
+
+fn flatten<T>(lhs: Result<Vec<T>, Box<dyn Error>>, rhs: Result<T, Box<dyn Error>>)
+  -> Result<Vec<T>, Box<dyn Error>>
+{
+    let mut lhs = lhs?;
+    let rhs = rhs?;
+    lhs.push(rhs);
+    Ok(lhs)
+}
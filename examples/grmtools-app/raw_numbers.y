@@ -0,0 +1,68 @@
+%start Member
+%expect-unused Unmatched "UNMATCHED"
+
+%%
+
+Object -> Result<RawNumberValue<'input>, Box<dyn Error>>:
+    "{" ObjectMembersOpt "}" { Ok(RawNumberValue::Object(RawNumberMap::from_iter($2?))) }
+  ;
+
+ObjectMembersOpt -> Result<Vec<RawNumberMember<'input>>, Box<dyn Error>>:
+    ObjectMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ObjectMembers -> Result<Vec<RawNumberMember<'input>>, Box<dyn Error>>:
+    ObjectMembers "," ObjectMember { flatten($1, $3) }
+  | ObjectMember { Ok(vec![$1?]) }
+  ;
+
+ObjectMember -> Result<RawNumberMember<'input>, Box<dyn Error>>:
+    "STRING" ":" Member {
+      let span = $1?.span();
+      Ok((crate::string(span, $lexer.span_str(span))?.into_owned(), $3?))
+    }
+  ;
+
+Member -> Result<RawNumberValue<'input>, Box<dyn Error>>:
+    "[" ArrayMembersOpt "]" { Ok(RawNumberValue::Array($2?)) }
+  | "FALSE" { Ok(RawNumberValue::Bool(false)) }
+  | "FLOAT" { Ok(RawNumberValue::Number($lexer.span_str($1?.span()))) }
+  | "NULL" { Ok(RawNumberValue::Null) }
+  | Object { $1 }
+  | "STRING" {
+      let span = $1?.span();
+      Ok(RawNumberValue::String(crate::string(span, $lexer.span_str(span))?.into_owned()))
+    }
+  | "TRUE" { Ok(RawNumberValue::Bool(true)) }
+  ;
+
+ArrayMembersOpt -> Result<Vec<RawNumberValue<'input>>, Box<dyn Error>>:
+    ArrayMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ArrayMembers -> Result<Vec<RawNumberValue<'input>>, Box<dyn Error>>:
+    ArrayMembers "," Member { flatten($1, $3) }
+  | Member { Ok(vec![$1?])}
+  ;
+
+Unmatched -> ():
+  "UNMATCHED" { }
+  ;
+
+%%
+
+use json_model::{RawNumberMap, RawNumberValue};
+use std::error::Error;
+
+type RawNumberMember<'input> = (String, RawNumberValue<'input>);
+
+fn flatten<T>(lhs: Result<Vec<T>, Box<dyn Error>>, rhs: Result<T, Box<dyn Error>>)
+  -> Result<Vec<T>, Box<dyn Error>>
+{
+    let mut lhs = lhs?;
+    let rhs = rhs?;
+    lhs.push(rhs);
+    Ok(lhs)
+}
@@ -0,0 +1,111 @@
+//! Parsing past syntax errors with `lrpar`'s CPCT+ error recovery, or stopping at the first
+//!
+//! CPCT+ finds every minimal sequence of insertions, deletions and shifts that lets the parse go
+//! on, continues with the first, and builds the value from the repaired input.
+
+use std::ops::Range;
+
+use json_model::{ParseError, Value};
+use lrlex::{DefaultLexeme, DefaultLexerTypes, LRNonStreamingLexer};
+use lrpar::{LexParseError, Lexeme as _, ParseRepair};
+
+use crate::{action_error, json_l, json_y, no_recovery_l, no_recovery_y, parse_error};
+
+/// What the parser does on a syntax error
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// Stop at the first error
+    None,
+    /// Repair the input with CPCT+ and keep parsing
+    CpctPlus,
+}
+
+/// One step of a repair sequence
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Repair {
+    /// Insert a token, by name
+    Insert(&'static str),
+    /// Delete the lexeme at this byte range
+    Delete(Range<usize>),
+    /// Keep the lexeme at this byte range
+    Shift(Range<usize>),
+}
+
+/// A syntax error and the ways found to repair it
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxError {
+    pub error: ParseError,
+    /// Every minimal repair sequence, the parse having continued with the first; empty without
+    /// recovery, and for errors in a string's escapes, which are only found once it is decoded
+    pub repairs: Vec<Vec<Repair>>,
+}
+
+/// What a parse with [`Recovery`] made of a document
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recovered {
+    /// The value, built from the repaired input if there were syntax errors; `None` if the
+    /// parse stopped at an error
+    pub value: Option<Value>,
+    /// In the order they were found
+    pub errors: Vec<SyntaxError>,
+}
+
+/// Parse `src`, handling syntax errors as `recovery` says
+pub fn parse(src: &str, recovery: Recovery) -> Recovered {
+    match recovery {
+        Recovery::None => {
+            let lexerdef = no_recovery_l::lexerdef();
+            let lexer = lexerdef.lexer(src);
+            let (res, errs) = no_recovery_y::parse(&lexer);
+            recovered(&lexer, res, &errs)
+        }
+        Recovery::CpctPlus => {
+            let lexerdef = json_l::lexerdef();
+            let lexer = lexerdef.lexer(src);
+            let (res, errs) = json_y::parse(&lexer);
+            recovered(&lexer, res, &errs)
+        }
+    }
+}
+
+fn recovered(
+    lexer: &LRNonStreamingLexer<'_, '_, DefaultLexerTypes<u32>>,
+    res: Option<Result<Value, Box<dyn std::error::Error>>>,
+    errs: &[LexParseError<u32, DefaultLexerTypes<u32>>],
+) -> Recovered {
+    let mut errors = errs
+        .iter()
+        .map(|err| SyntaxError {
+            error: parse_error(lexer, err),
+            repairs: match err {
+                LexParseError::LexError(_) => Vec::new(),
+                LexParseError::ParseError(e) => e
+                    .repairs()
+                    .iter()
+                    .map(|seq| seq.iter().map(repair).collect())
+                    .collect(),
+            },
+        })
+        .collect::<Vec<_>>();
+    let value = match res {
+        Some(Ok(value)) => Some(value),
+        Some(Err(err)) => {
+            errors.push(SyntaxError {
+                error: action_error(err),
+                repairs: Vec::new(),
+            });
+            None
+        }
+        None => None,
+    };
+    Recovered { value, errors }
+}
+
+fn repair(repair: &ParseRepair<DefaultLexeme<u32>, u32>) -> Repair {
+    let range = |lexeme: &DefaultLexeme<u32>| lexeme.span().start()..lexeme.span().end();
+    match repair {
+        ParseRepair::Insert(tidx) => Repair::Insert(json_y::token_epp(*tidx).unwrap_or("?")),
+        ParseRepair::Delete(lexeme) => Repair::Delete(range(lexeme)),
+        ParseRepair::Shift(lexeme) => Repair::Shift(range(lexeme)),
+    }
+}
@@ -0,0 +1,77 @@
+%start Member
+%expect-unused Unmatched "UNMATCHED"
+
+%%
+
+Object -> Result<Spanned<SpannedValue>, Box<dyn Error>>:
+    "{" ObjectMembersOpt "}" { Ok(spanned($span, SpannedValue::Object($2?))) }
+  ;
+
+ObjectMembersOpt -> Result<Vec<SpannedMember>, Box<dyn Error>>:
+    ObjectMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ObjectMembers -> Result<Vec<SpannedMember>, Box<dyn Error>>:
+    ObjectMembers "," ObjectMember { flatten($1, $3) }
+  | ObjectMember { Ok(vec![$1?]) }
+  ;
+
+ObjectMember -> Result<SpannedMember, Box<dyn Error>>:
+    "STRING" ":" Member {
+      let span = $1?.span();
+      let s = crate::string(span, $lexer.span_str(span))?.into_owned();
+      Ok((spanned(span, s), $3?))
+    }
+  ;
+
+Member -> Result<Spanned<SpannedValue>, Box<dyn Error>>:
+    "[" ArrayMembersOpt "]" { Ok(spanned($span, SpannedValue::Array($2?))) }
+  | "FALSE" { Ok(spanned($span, SpannedValue::Bool(false))) }
+  | "FLOAT" {
+      let n = $lexer.span_str($1?.span()).parse::<f64>().unwrap();
+      Ok(spanned($span, SpannedValue::Number(n)))
+    }
+  | "NULL" { Ok(spanned($span, SpannedValue::Null)) }
+  | Object { $1 }
+  | "STRING" {
+      let span = $1?.span();
+      let s = crate::string(span, $lexer.span_str(span))?.into_owned();
+      Ok(spanned($span, SpannedValue::String(s)))
+    }
+  | "TRUE" { Ok(spanned($span, SpannedValue::Bool(true))) }
+  ;
+
+ArrayMembersOpt -> Result<Vec<Spanned<SpannedValue>>, Box<dyn Error>>:
+    ArrayMembers { $1 }
+  | { Ok(Vec::new()) }
+  ;
+
+ArrayMembers -> Result<Vec<Spanned<SpannedValue>>, Box<dyn Error>>:
+    ArrayMembers "," Member { flatten($1, $3) }
+  | Member { Ok(vec![$1?])}
+  ;
+
+Unmatched -> ():
+  "UNMATCHED" { }
+  ;
+
+%%
+
+// `json.y` with a span on every value, from `$span`
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+use std::error::Error;
+
+fn spanned<T>(span: cfgrammar::Span, value: T) -> Spanned<T> {
+    Spanned::new(value, span.start()..span.end())
+}
+
+fn flatten<T>(lhs: Result<Vec<T>, Box<dyn Error>>, rhs: Result<T, Box<dyn Error>>)
+  -> Result<Vec<T>, Box<dyn Error>>
+{
+    let mut lhs = lhs?;
+    let rhs = rhs?;
+    lhs.push(rhs);
+    Ok(lhs)
+}
@@ -0,0 +1,359 @@
+//! Deterministic synthetic JSON documents
+//!
+//! The same [`Config`] (including its `seed`) always produces the same document, on every host,
+//! so generated inputs can stand in for vendored files in benchmarks and tests.
+
+use std::fmt::Write as _;
+
+/// The shape of a generated document
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub seed: u64,
+    /// Approximate document size in bytes; the document stops growing once it is reached
+    pub size: usize,
+    /// Maximum nesting of arrays and objects below the top-level `{"data": [...]}`
+    pub depth: usize,
+    /// Minimum number of members in an array or object
+    pub min_breadth: usize,
+    /// Maximum number of members in an array or object
+    pub breadth: usize,
+    /// Maximum string length, in characters or escape sequences
+    pub string_len: usize,
+    /// Chance, from 0 to 1, that a string character is an escape sequence
+    pub escape_ratio: f64,
+    /// Chance, from 0 to 1, that an unescaped string character is non-ASCII
+    pub unicode_ratio: f64,
+    pub numbers: Numbers,
+    pub weights: Weights,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            size: 64 * 1024,
+            depth: 6,
+            min_breadth: 0,
+            breadth: 8,
+            string_len: 24,
+            escape_ratio: 0.02,
+            unicode_ratio: 0.02,
+            numbers: Numbers::default(),
+            weights: Weights::default(),
+        }
+    }
+}
+
+/// Which number syntaxes may appear
+#[derive(Copy, Clone, Debug)]
+pub struct Numbers {
+    /// `-` signs
+    pub negative: bool,
+    /// Add a fractional part, e.g. `12.5`
+    pub fraction: bool,
+    /// `e10` style exponents
+    pub exponent: bool,
+}
+
+impl Default for Numbers {
+    fn default() -> Self {
+        Self {
+            negative: true,
+            fraction: true,
+            exponent: true,
+        }
+    }
+}
+
+/// Relative chance of each kind of value; containers are only chosen while `depth` allows
+#[derive(Copy, Clone, Debug)]
+pub struct Weights {
+    pub null: u32,
+    pub bool: u32,
+    pub number: u32,
+    pub string: u32,
+    pub array: u32,
+    pub object: u32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            null: 1,
+            bool: 2,
+            number: 4,
+            string: 4,
+            array: 2,
+            object: 2,
+        }
+    }
+}
+
+/// Generate a document with the shape of `config`
+///
+/// The document is always an object, `{"data": [...]}`.
+pub fn generate(config: &Config) -> String {
+    let mut gen = Generator {
+        config,
+        rng: Rng::new(config.seed),
+        out: String::with_capacity(config.size + 1024),
+    };
+    gen.out.push_str("{\"data\": [\n");
+    let mut first = true;
+    while first || gen.out.len() < config.size {
+        if !first {
+            gen.out.push_str(",\n");
+        }
+        first = false;
+        gen.value(config.depth);
+    }
+    gen.out.push_str("\n]}\n");
+    gen.out
+}
+
+struct Generator<'c> {
+    config: &'c Config,
+    rng: Rng,
+    out: String,
+}
+
+impl Generator<'_> {
+    fn value(&mut self, depth: usize) {
+        let w = self.config.weights;
+        let (array, object) = if depth == 0 {
+            (0, 0)
+        } else {
+            (w.array, w.object)
+        };
+        let weights = [w.null, w.bool, w.number, w.string, array, object];
+        let total = weights.iter().sum::<u32>().max(1);
+        let mut pick = self.rng.below(total as usize) as u32;
+        let kind = weights
+            .iter()
+            .position(|w| {
+                if pick < *w {
+                    true
+                } else {
+                    pick -= w;
+                    false
+                }
+            })
+            .unwrap_or(0);
+        match kind {
+            0 => self.out.push_str("null"),
+            1 => self.out.push_str(if self.rng.below(2) == 0 {
+                "true"
+            } else {
+                "false"
+            }),
+            2 => self.number(),
+            3 => self.string(""),
+            4 => self.array(depth - 1),
+            _ => self.object(depth - 1),
+        }
+    }
+
+    fn array(&mut self, depth: usize) {
+        self.out.push('[');
+        for i in 0..self.breadth() {
+            if i != 0 {
+                self.out.push_str(", ");
+            }
+            self.value(depth);
+        }
+        self.out.push(']');
+    }
+
+    fn object(&mut self, depth: usize) {
+        self.out.push('{');
+        for i in 0..self.breadth() {
+            if i != 0 {
+                self.out.push_str(", ");
+            }
+            // The index suffix keeps keys unique, whatever else is drawn
+            self.string(&format!("_{i}"));
+            self.out.push_str(": ");
+            self.value(depth);
+        }
+        self.out.push('}');
+    }
+
+    fn breadth(&mut self) -> usize {
+        let min = self.config.min_breadth.min(self.config.breadth);
+        min + self.rng.below(self.config.breadth - min + 1)
+    }
+
+    fn number(&mut self) {
+        let numbers = self.config.numbers;
+        if numbers.negative && self.rng.below(4) == 0 {
+            self.out.push('-');
+        }
+        let int = match self.rng.below(4) {
+            0 => self.rng.below(10),
+            1 => self.rng.below(1_000),
+            2 => self.rng.below(1_000_000),
+            _ => self.rng.below(1 << 53),
+        };
+        let _ = write!(self.out, "{int}");
+        if numbers.fraction && self.rng.below(2) == 0 {
+            let _ = write!(self.out, ".{}", self.rng.below(1_000_000));
+        }
+        if numbers.exponent && self.rng.below(5) == 0 {
+            let e = ["e", "E", "e+", "e-", "E-"][self.rng.below(5)];
+            let _ = write!(self.out, "{e}{}", self.rng.below(30));
+        }
+    }
+
+    fn string(&mut self, suffix: &str) {
+        self.out.push('"');
+        for _ in 0..self.rng.below(self.config.string_len + 1) {
+            if self.rng.chance(self.config.escape_ratio) {
+                self.escape();
+            } else if self.rng.chance(self.config.unicode_ratio) {
+                let c = self.non_ascii();
+                self.out.push(c);
+            } else {
+                let c = ALPHABET[self.rng.below(ALPHABET.len())];
+                self.out.push(c as char);
+            }
+        }
+        self.out.push_str(suffix);
+        self.out.push('"');
+    }
+
+    fn escape(&mut self) {
+        match self.rng.below(11) {
+            0 => self.out.push_str(r#"\""#),
+            1 => self.out.push_str(r"\\"),
+            2 => self.out.push_str(r"\/"),
+            3 => self.out.push_str(r"\b"),
+            4 => self.out.push_str(r"\f"),
+            5 => self.out.push_str(r"\n"),
+            6 => self.out.push_str(r"\r"),
+            7 => self.out.push_str(r"\t"),
+            8 => {
+                let c = self.rng.below(0x20);
+                let _ = write!(self.out, "\\u{c:04x}");
+            }
+            9 => {
+                let c = self.non_ascii();
+                self.utf16_escape(c);
+            }
+            _ => {
+                // Always a surrogate pair
+                let c = char::from_u32(0x1_F300 + self.rng.below(0x300) as u32).unwrap_or('🦀');
+                self.utf16_escape(c);
+            }
+        }
+    }
+
+    fn utf16_escape(&mut self, c: char) {
+        let mut units = [0; 2];
+        for unit in c.encode_utf16(&mut units) {
+            let _ = write!(self.out, "\\u{unit:04X}");
+        }
+    }
+
+    fn non_ascii(&mut self) -> char {
+        let (start, len) = UNICODE_RANGES[self.rng.below(UNICODE_RANGES.len())];
+        char::from_u32(start + self.rng.below(len as usize) as u32).unwrap_or('\u{FFFD}')
+    }
+}
+
+const ALPHABET: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 _-.,:;!?'()[]{}";
+
+/// `(start, len)` of code point ranges, none of which include surrogates
+const UNICODE_RANGES: &[(u32, u32)] = &[
+    (0x00C0, 0x40),   // Latin-1 letters
+    (0x0391, 0x19),   // Greek
+    (0x0410, 0x40),   // Cyrillic
+    (0x05D0, 0x1B),   // Hebrew
+    (0x3041, 0x56),   // Hiragana
+    (0x4E00, 0x5000), // CJK
+    (0xAC00, 0x2000), // Hangul
+    (0x1_F600, 0x50), // Emoji
+];
+
+/// SplitMix64, chosen for being tiny and having no dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, or `0` when `n == 0`
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        p > 0.0 && ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth(value: &serde_json::Value) -> usize {
+        match value {
+            serde_json::Value::Array(items) => 1 + items.iter().map(depth).max().unwrap_or(0),
+            serde_json::Value::Object(members) => {
+                1 + members.values().map(depth).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_document() {
+        let config = Config::default();
+        assert_eq!(generate(&config), generate(&config));
+        let other = Config {
+            seed: 1,
+            ..Config::default()
+        };
+        assert_ne!(generate(&config), generate(&other));
+    }
+
+    #[test]
+    fn documents_are_valid() {
+        for seed in 0..20 {
+            let config = Config {
+                seed,
+                escape_ratio: 0.2,
+                unicode_ratio: 0.2,
+                ..Config::default()
+            };
+            let doc = generate(&config);
+            let value: serde_json::Value =
+                serde_json::from_str(&doc).unwrap_or_else(|e| panic!("seed {seed}: {e}\n{doc}"));
+            // The top-level object and its `data` array
+            assert!(depth(&value) <= config.depth + 2, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn size_is_approximate() {
+        let config = Config {
+            size: 100_000,
+            ..Config::default()
+        };
+        let len = generate(&config).len();
+        assert!((100_000..110_000).contains(&len), "{len}");
+    }
+}
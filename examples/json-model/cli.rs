@@ -0,0 +1,213 @@
+//! The command line shared by every `<name>-app`
+//!
+//! ```text
+//! <name>-app [--errors=text|json] [--mode=value|spanned|borrowed|raw-numbers|ordered|unique-keys|recover|no-recovery] [--max-depth=N] <file>
+//! ```
+//!
+//! Modes other than `value` are only available in apps built with the feature of the same name;
+//! `unique-keys` comes with `ordered`, and `no-recovery` with `recover`. `--max-depth` is how deeply
+//! arrays and objects may nest, [`MAX_DEPTH`] unless given, in apps whose parsers have a limit.
+//!
+//! Apps end with one of the [`Exit`] codes so that scripts can tell why a run failed.
+
+use std::fmt;
+
+use crate::{DuplicateKeys, ErrorReport, ParseError, MAX_DEPTH};
+
+/// Exit codes shared by every app
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    /// The document was rejected
+    Parse = 1,
+    /// The command line was invalid
+    Usage = 2,
+    /// The input couldn't be read
+    Io = 3,
+}
+
+impl Exit {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+/// How an app reports a rejected document
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Whatever the parser renders natively
+    #[default]
+    Text,
+    /// One [`ErrorReport`] per line, as JSON
+    Json,
+}
+
+/// What an app builds from the document
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// The app's usual output
+    #[default]
+    Value,
+    /// A [`SpannedValue`](crate::SpannedValue), through [`SpannedParser`](crate::SpannedParser)
+    Spanned,
+    /// A [`BorrowedValue`](crate::BorrowedValue), through [`BorrowedParser`](crate::BorrowedParser)
+    Borrowed,
+    /// A [`RawNumberValue`](crate::RawNumberValue), through
+    /// [`RawNumberParser`](crate::RawNumberParser)
+    RawNumbers,
+    /// An [`OrderedValue`](crate::OrderedValue) keeping duplicate keys, through
+    /// [`OrderedParser`](crate::OrderedParser)
+    Ordered,
+    /// An [`OrderedValue`](crate::OrderedValue), rejecting duplicate keys
+    UniqueKeys,
+    /// Repair syntax errors and keep parsing, reporting each repair
+    Recover,
+    /// Stop at the first syntax error, to compare with [`Mode::Recover`]
+    NoRecovery,
+}
+
+impl Mode {
+    const ALL: &'static [Mode] = &[
+        Mode::Value,
+        Mode::Spanned,
+        Mode::Borrowed,
+        Mode::RawNumbers,
+        Mode::Ordered,
+        Mode::UniqueKeys,
+        Mode::Recover,
+        Mode::NoRecovery,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Value => "value",
+            Mode::Spanned => "spanned",
+            Mode::Borrowed => "borrowed",
+            Mode::RawNumbers => "raw-numbers",
+            Mode::Ordered => "ordered",
+            Mode::UniqueKeys => "unique-keys",
+            Mode::Recover => "recover",
+            Mode::NoRecovery => "no-recovery",
+        }
+    }
+
+    /// What to do with duplicate keys, for the modes that build an
+    /// [`OrderedValue`](crate::OrderedValue)
+    pub fn duplicate_keys(self) -> Option<DuplicateKeys> {
+        match self {
+            Mode::Ordered => Some(DuplicateKeys::Keep),
+            Mode::UniqueKeys => Some(DuplicateKeys::Reject),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Args {
+    pub path: String,
+    pub errors: ErrorFormat,
+    pub mode: Mode,
+    pub max_depth: usize,
+}
+
+impl Args {
+    /// Parse [`std::env::args`], exiting with [`Exit::Usage`] on misuse
+    ///
+    /// `modes` are the app's modes besides [`Mode::Value`], which is always supported.
+    pub fn from_env(modes: &[Mode]) -> Self {
+        Self::parse(std::env::args().skip(1), modes).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            eprintln!("Usage: <app> [--errors=text|json] [--mode=value|spanned|borrowed|raw-numbers|ordered|unique-keys|recover|no-recovery] [--max-depth=N] <file>");
+            Exit::Usage.exit()
+        })
+    }
+
+    fn parse(args: impl IntoIterator<Item = String>, modes: &[Mode]) -> Result<Self, String> {
+        let mut path = None;
+        let mut errors = ErrorFormat::default();
+        let mut mode = Mode::default();
+        let mut max_depth = MAX_DEPTH;
+        for arg in args {
+            if let Some(name) = arg.strip_prefix("--mode=") {
+                mode = *Mode::ALL
+                    .iter()
+                    .find(|m| m.name() == name)
+                    .ok_or_else(|| format!("Unknown mode `{name}`"))?;
+                if mode != Mode::Value && !modes.contains(&mode) {
+                    return Err(format!("This app was built without `{name}` support"));
+                }
+                continue;
+            }
+            if let Some(depth) = arg.strip_prefix("--max-depth=") {
+                max_depth = depth
+                    .parse()
+                    .map_err(|_| format!("Invalid depth `{depth}`, expected a number"))?;
+                continue;
+            }
+            match arg.strip_prefix("--errors=") {
+                Some("text") => errors = ErrorFormat::Text,
+                Some("json") => errors = ErrorFormat::Json,
+                Some(other) => {
+                    return Err(format!(
+                        "Unknown error format `{other}`, expected `text` or `json`"
+                    ))
+                }
+                None if path.is_none() => path = Some(arg),
+                None => return Err(format!("Unexpected argument `{arg}`")),
+            }
+        }
+        Ok(Self {
+            path: path.ok_or("Expected file argument")?,
+            errors,
+            mode,
+            max_depth,
+        })
+    }
+}
+
+/// Read the document at `path`, exiting with [`Exit::Io`] on failure
+pub fn read_input(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|err| {
+        eprintln!("Failed to read {path}: {err}");
+        Exit::Io.exit()
+    })
+}
+
+/// Print `err`'s [`ErrorReport`] to stderr
+pub fn print_report(src: &str, err: &ParseError) {
+    eprintln!("{}", ErrorReport::new(src, err).to_json());
+}
+
+/// Parse `src` for one of the [`Mode`]s beyond [`Mode::Value`] and exit
+///
+/// Output matches the apps' own: the result is printed in debug builds and discarded otherwise.
+pub fn run<'a, T: fmt::Debug>(
+    args: &Args,
+    src: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<T, ParseError>,
+) -> ! {
+    match parse(src) {
+        Ok(value) => {
+            #[cfg(debug_assertions)]
+            {
+                println!("{value:#?}");
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                std::hint::black_box(value);
+            }
+            Exit::Success.exit()
+        }
+        Err(err) => {
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{err}"),
+                ErrorFormat::Json => print_report(src, &err),
+            }
+            Exit::Parse.exit()
+        }
+    }
+}
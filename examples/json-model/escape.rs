@@ -0,0 +1,187 @@
+//! Decoding of JSON string escapes, for parsers that only recognize a string's extent
+//!
+//! Lone surrogates (a `\uD800`-`\uDFFF` escape without its other half) can't be represented in a
+//! Rust string, so they are rejected, as `serde_json` does.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::ParseError;
+
+/// Decode the escapes in `raw`, the text between a string's quotes, borrowing it if there are none
+pub fn unescape(raw: &str) -> Result<Cow<'_, str>, EscapeError> {
+    let Some(first) = raw.find('\\') else {
+        return Ok(Cow::Borrowed(raw));
+    };
+
+    let mut decoded = String::with_capacity(raw.len());
+    let mut i = first;
+    decoded.push_str(&raw[..i]);
+    while i < raw.len() {
+        let Some(len) = raw[i..].find('\\') else {
+            decoded.push_str(&raw[i..]);
+            break;
+        };
+        decoded.push_str(&raw[i..i + len]);
+        let start = i + len;
+        let error = |kind| EscapeError {
+            offset: start,
+            kind,
+        };
+
+        let Some(escape) = raw[start + 1..].chars().next() else {
+            return Err(error(EscapeErrorKind::Unfinished));
+        };
+        i = start + 1 + escape.len_utf8();
+        let c = match escape {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\x08',
+            'f' => '\x0C',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = hex4(raw, i).ok_or_else(|| error(EscapeErrorKind::InvalidUnicode))?;
+                i += 4;
+                match high {
+                    0xD800..=0xDBFF => {
+                        let low = raw[i..]
+                            .strip_prefix("\\u")
+                            .and_then(|_| hex4(raw, i + 2))
+                            .filter(|low| (0xDC00..=0xDFFF).contains(low))
+                            .ok_or_else(|| error(EscapeErrorKind::LoneSurrogate(high)))?;
+                        i += 6;
+                        let c = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+                        char::from_u32(c).expect("a surrogate pair is a valid char")
+                    }
+                    0xDC00..=0xDFFF => return Err(error(EscapeErrorKind::LoneSurrogate(high))),
+                    _ => char::from_u32(high).expect("not a surrogate"),
+                }
+            }
+            c => return Err(error(EscapeErrorKind::Unknown(c))),
+        };
+        decoded.push(c);
+    }
+    Ok(Cow::Owned(decoded))
+}
+
+/// The four hex digits at `raw[i..]`
+fn hex4(raw: &str, i: usize) -> Option<u32> {
+    let digits = raw.get(i..i + 4)?;
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Why [`unescape`] rejected a string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscapeError {
+    /// Byte offset of the escape's backslash in the text passed to [`unescape`]
+    pub offset: usize,
+    pub kind: EscapeErrorKind,
+}
+
+/// What is wrong with an escape
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EscapeErrorKind {
+    /// The text ends with a backslash
+    Unfinished,
+    /// The backslash is followed by something other than an escape
+    Unknown(char),
+    /// `\u` isn't followed by four hex digits
+    InvalidUnicode,
+    /// The half of a surrogate pair that is missing its other half
+    LoneSurrogate(u32),
+}
+
+impl EscapeError {
+    /// A [`ParseError`] for the string whose text starts at `start` in the input
+    pub fn into_parse_error(self, start: usize) -> ParseError {
+        ParseError::new(self.to_string())
+            .with_offset(start + self.offset)
+            .with_expected(["escape sequence"])
+    }
+}
+
+impl fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EscapeErrorKind::Unfinished => write!(f, "unfinished escape"),
+            EscapeErrorKind::Unknown(c) => write!(f, "invalid escape `\\{c}`"),
+            EscapeErrorKind::InvalidUnicode => {
+                write!(f, "`\\u` must be followed by four hex digits")
+            }
+            EscapeErrorKind::LoneSurrogate(half) => write!(f, "lone surrogate `\\u{half:04X}`"),
+        }
+    }
+}
+
+impl std::error::Error for EscapeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(offset: usize, kind: EscapeErrorKind) -> Result<Cow<'static, str>, EscapeError> {
+        Err(EscapeError { offset, kind })
+    }
+
+    #[test]
+    fn borrows_without_escapes() {
+        assert!(matches!(unescape(""), Ok(Cow::Borrowed(""))));
+        assert!(matches!(unescape("plain é"), Ok(Cow::Borrowed("plain é"))));
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            unescape(r#"a\"b\\c\/d\be\ff\ng\rh\ti"#).unwrap(),
+            "a\"b\\c/d\x08e\x0Cf\ng\rh\ti"
+        );
+        assert_eq!(unescape(r"\u0041\u00e9\u2014").unwrap(), "Aé—");
+        assert_eq!(unescape(r"\u0000").unwrap(), "\0");
+    }
+
+    #[test]
+    fn combines_surrogate_pairs() {
+        assert_eq!(unescape(r"\uD83D\uDE10").unwrap(), "😐");
+        assert_eq!(unescape(r"x\ud834\udd1ey").unwrap(), "x𝄞y");
+        assert_eq!(unescape(r"\uDBFF\uDFFF").unwrap(), "\u{10FFFF}");
+    }
+
+    #[test]
+    fn rejects_lone_surrogates() {
+        let lone = EscapeErrorKind::LoneSurrogate;
+        assert_eq!(unescape(r"\uD800"), error(0, lone(0xD800)));
+        assert_eq!(unescape(r"ab\uDC00"), error(2, lone(0xDC00)));
+        assert_eq!(unescape(r"\uD800A"), error(0, lone(0xD800)));
+        assert_eq!(unescape(r"\uD800\uD800"), error(0, lone(0xD800)));
+        assert_eq!(unescape(r"\uD800\n"), error(0, lone(0xD800)));
+    }
+
+    #[test]
+    fn rejects_invalid_escapes() {
+        assert_eq!(unescape("a\\"), error(1, EscapeErrorKind::Unfinished));
+        assert_eq!(unescape(r"\x"), error(0, EscapeErrorKind::Unknown('x')));
+        assert_eq!(unescape("\\é"), error(0, EscapeErrorKind::Unknown('é')));
+        assert_eq!(unescape(r"\u12"), error(0, EscapeErrorKind::InvalidUnicode));
+        assert_eq!(
+            unescape(r"\u+123"),
+            error(0, EscapeErrorKind::InvalidUnicode)
+        );
+        assert_eq!(
+            unescape(r"\u12G4"),
+            error(0, EscapeErrorKind::InvalidUnicode)
+        );
+    }
+
+    #[test]
+    fn error_offset_is_in_input() {
+        let err = unescape(r"ok\q").unwrap_err().into_parse_error(10);
+        assert_eq!(err.offset, Some(12));
+        assert_eq!(err.message, r"invalid escape `\q`");
+    }
+}
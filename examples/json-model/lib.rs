@@ -0,0 +1,503 @@
+//! The canonical JSON value shared by every example app
+//!
+//! Each parser builds (or converts into) [`Value`] so that their results can be compared with
+//! each other, and implements [`JsonParser`] so they can all be driven from a single process.
+
+pub mod cli;
+pub mod escape;
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+/// How deeply arrays and objects may nest in parsers with a depth limit
+///
+/// Deeper documents are rejected rather than risking a stack overflow; `serde_json` stops one
+/// level earlier.
+pub const MAX_DEPTH: usize = 128;
+
+/// The offset of the first `[` or `{` in `src` nested more than `max_depth` deep, if any
+///
+/// For parsers that can't count levels as they go, to reject deep input before they recurse into
+/// it. Only brackets outside strings are counted and nothing else is checked, so a document that
+/// is also malformed may be reported as too deep first.
+pub fn too_deep(src: &str, max_depth: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (offset, byte) in src.bytes().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' if depth == max_depth => return Some(offset),
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// [`too_deep`] as a [`ParseError`], for parsers that recurse without counting levels
+///
+/// Called before parsing so that deep input is rejected instead of overflowing the stack.
+pub fn check_depth(src: &str, max_depth: usize) -> Result<(), ParseError> {
+    match too_deep(src, max_depth) {
+        Some(offset) => Err(ParseError::new("nesting too deep").with_offset(offset)),
+        None => Ok(()),
+    }
+}
+
+/// The members of a JSON object
+pub type Map = HashMap<String, Value>;
+
+/// Any valid JSON value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Map),
+}
+
+/// A JSON parser that can be called in-process
+pub trait JsonParser {
+    /// The parser's name, as used for its `<name>-app` directory
+    fn name(&self) -> &'static str;
+
+    /// Parse `src` into the canonical [`Value`]
+    fn parse(&self, src: &str) -> Result<Value, ParseError>;
+}
+
+/// A [`JsonParser`] that can also record where in the input each value came from
+pub trait SpannedParser: JsonParser {
+    /// Parse `src` into a [`SpannedValue`] covering the whole document
+    fn parse_spanned(&self, src: &str) -> Result<Spanned<SpannedValue>, ParseError>;
+}
+
+/// Byte range of a value in its input
+pub type Span = Range<usize>;
+
+/// `value` and the bytes of the input it was parsed from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(value: T, span: Span) -> Self {
+        Self { value, span }
+    }
+}
+
+/// [`Value`] with a [`Span`] on every value and object key
+///
+/// Strings span their quotes and containers span their brackets. Members stay in input order,
+/// duplicates included, since each has its own location.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpannedValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Spanned<SpannedValue>>),
+    Object(Vec<SpannedMember>),
+}
+
+/// A key and value of a [`SpannedValue::Object`]
+pub type SpannedMember = (Spanned<String>, Spanned<SpannedValue>);
+
+/// A [`JsonParser`] that can also borrow strings from its input instead of copying them
+pub trait BorrowedParser: JsonParser {
+    /// Parse `src` into a [`BorrowedValue`]
+    fn parse_borrowed<'a>(&self, src: &'a str) -> Result<BorrowedValue<'a>, ParseError>;
+}
+
+/// The members of a [`BorrowedValue::Object`]
+pub type BorrowedMap<'a> = HashMap<Cow<'a, str>, BorrowedValue<'a>>;
+
+/// [`Value`] with strings and keys borrowed from the input
+///
+/// A string is only [`Cow::Owned`] when the parser had to build it, e.g. to decode an escape.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BorrowedValue<'a> {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(Cow<'a, str>),
+    Array(Vec<BorrowedValue<'a>>),
+    Object(BorrowedMap<'a>),
+}
+
+/// A [`JsonParser`] that can also keep numbers as their text instead of rounding them to `f64`
+pub trait RawNumberParser: JsonParser {
+    /// Parse `src` into a [`RawNumberValue`]
+    fn parse_raw_numbers<'a>(&self, src: &'a str) -> Result<RawNumberValue<'a>, ParseError>;
+}
+
+/// The members of a [`RawNumberValue::Object`]
+pub type RawNumberMap<'a> = HashMap<String, RawNumberValue<'a>>;
+
+/// [`Value`] with every number kept as its text in the input, so no precision is lost
+#[derive(Clone, Debug, PartialEq)]
+pub enum RawNumberValue<'a> {
+    Null,
+    Bool(bool),
+    Number(&'a str),
+    String(String),
+    Array(Vec<RawNumberValue<'a>>),
+    Object(RawNumberMap<'a>),
+}
+
+/// A [`JsonParser`] that can also keep object members in input order
+pub trait OrderedParser: JsonParser {
+    /// Parse `src` into an [`OrderedValue`], treating a key repeated within an object as `keys`
+    /// says
+    fn parse_ordered(&self, src: &str, keys: DuplicateKeys) -> Result<OrderedValue, ParseError>;
+}
+
+/// What an [`OrderedParser`] does with a key that appears twice in the same object
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Keep every member, as [`SpannedValue`] does
+    Keep,
+    /// Reject the document
+    Reject,
+}
+
+impl DuplicateKeys {
+    /// Under [`DuplicateKeys::Reject`], find the first key in `members` seen before
+    pub fn check(self, members: &OrderedMap) -> Result<(), DuplicateKey> {
+        if self == DuplicateKeys::Keep || members.len() < 2 {
+            return Ok(());
+        }
+        let mut seen = HashSet::with_capacity(members.len());
+        match members.iter().find(|(key, _)| !seen.insert(key.as_str())) {
+            Some((key, _)) => Err(DuplicateKey(key.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A key that appeared twice in the same object, under [`DuplicateKeys::Reject`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateKey(pub String);
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate key {:?}", self.0)
+    }
+}
+
+impl std::error::Error for DuplicateKey {}
+
+/// The members of an [`OrderedValue::Object`], in input order
+pub type OrderedMap = Vec<(String, OrderedValue)>;
+
+/// [`Value`] with object members kept in the order they were written
+#[derive(Clone, Debug, PartialEq)]
+pub enum OrderedValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<OrderedValue>),
+    Object(OrderedMap),
+}
+
+/// Why a parser rejected its input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the input, if the parser reports one
+    pub offset: Option<usize>,
+    /// What would have been accepted at `offset`, in the parser's own terms
+    pub expected: Vec<String>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            offset: None,
+            expected: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn with_offset(mut self, offset: impl Into<Option<usize>>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    /// Record the expected set, sorted and deduplicated
+    pub fn with_expected(mut self, expected: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.expected = expected.into_iter().map(Into::into).collect();
+        self.expected.sort();
+        self.expected.dedup();
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A [`ParseError`] located in its input, for tools comparing parsers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub offset: Option<usize>,
+    /// 1-based line of `offset`
+    pub line: Option<usize>,
+    /// 1-based column of `offset`, in characters
+    pub column: Option<usize>,
+    pub expected: Vec<String>,
+    pub message: String,
+}
+
+impl ErrorReport {
+    pub fn new(src: &str, err: &ParseError) -> Self {
+        let position = err.offset.map(|offset| {
+            let mut end = offset.min(src.len());
+            while !src.is_char_boundary(end) {
+                end -= 1;
+            }
+            let before = &src[..end];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let line = before.matches('\n').count() + 1;
+            let column = before[line_start..].chars().count() + 1;
+            (line, column)
+        });
+        Self {
+            offset: err.offset,
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            expected: err.expected.clone(),
+            message: err.message.clone(),
+        }
+    }
+
+    /// The report as a single-line JSON object
+    pub fn to_json(&self) -> String {
+        fn number(n: Option<usize>) -> String {
+            n.map_or_else(|| "null".to_owned(), |n| n.to_string())
+        }
+
+        let expected = self
+            .expected
+            .iter()
+            .map(|e| json_string(e))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "{{\"offset\": {}, \"line\": {}, \"column\": {}, \"expected\": [{expected}], \"message\": {}}}",
+            number(self.offset),
+            number(self.line),
+            number(self.column),
+            json_string(&self.message),
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Convert a parser's own output into the canonical [`Value`]
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+/// Drops the spans; of duplicate keys the last wins, as with [`Map::insert`]
+impl IntoValue for Spanned<SpannedValue> {
+    fn into_value(self) -> Value {
+        match self.value {
+            SpannedValue::Null => Value::Null,
+            SpannedValue::Bool(b) => Value::Bool(b),
+            SpannedValue::Number(n) => Value::Number(n),
+            SpannedValue::String(s) => Value::String(s),
+            SpannedValue::Array(a) => {
+                Value::Array(a.into_iter().map(IntoValue::into_value).collect())
+            }
+            SpannedValue::Object(o) => Value::Object(
+                o.into_iter()
+                    .map(|(k, v)| (k.value, v.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl IntoValue for BorrowedValue<'_> {
+    fn into_value(self) -> Value {
+        match self {
+            BorrowedValue::Null => Value::Null,
+            BorrowedValue::Bool(b) => Value::Bool(b),
+            BorrowedValue::Number(n) => Value::Number(n),
+            BorrowedValue::String(s) => Value::String(s.into_owned()),
+            BorrowedValue::Array(a) => {
+                Value::Array(a.into_iter().map(IntoValue::into_value).collect())
+            }
+            BorrowedValue::Object(o) => Value::Object(
+                o.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Rounds numbers to the nearest `f64`, with [`str::parse`]
+impl IntoValue for RawNumberValue<'_> {
+    fn into_value(self) -> Value {
+        match self {
+            RawNumberValue::Null => Value::Null,
+            RawNumberValue::Bool(b) => Value::Bool(b),
+            RawNumberValue::Number(n) => {
+                Value::Number(n.parse().expect("a JSON number is a valid `f64`"))
+            }
+            RawNumberValue::String(s) => Value::String(s),
+            RawNumberValue::Array(a) => {
+                Value::Array(a.into_iter().map(IntoValue::into_value).collect())
+            }
+            RawNumberValue::Object(o) => {
+                Value::Object(o.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+            }
+        }
+    }
+}
+
+/// Of duplicate keys the last wins, as with [`Map::insert`]
+impl IntoValue for OrderedValue {
+    fn into_value(self) -> Value {
+        match self {
+            OrderedValue::Null => Value::Null,
+            OrderedValue::Bool(b) => Value::Bool(b),
+            OrderedValue::Number(n) => Value::Number(n),
+            OrderedValue::String(s) => Value::String(s),
+            OrderedValue::Array(a) => {
+                Value::Array(a.into_iter().map(IntoValue::into_value).collect())
+            }
+            OrderedValue::Object(o) => {
+                Value::Object(o.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl IntoValue for serde_json::Value {
+    fn into_value(self) -> Value {
+        match self {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => {
+                Value::Number(n.as_f64().expect("`arbitrary_precision` is not enabled"))
+            }
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(a) => {
+                Value::Array(a.into_iter().map(IntoValue::into_value).collect())
+            }
+            serde_json::Value::Object(o) => {
+                Value::Object(o.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_locates_offset() {
+        let src = "{\n  \"é\": [1,, 2]\n}";
+        let err = ParseError::new("unexpected `,`")
+            .with_offset(src.find(",,").unwrap() + 1)
+            .with_expected(["value", "']'", "value"]);
+        let report = ErrorReport::new(src, &err);
+        assert_eq!(report.line, Some(2));
+        // `é` is two bytes but one column
+        assert_eq!(report.column, Some(11));
+        assert_eq!(report.expected, ["']'", "value"]);
+        assert_eq!(
+            report.to_json(),
+            r#"{"offset": 13, "line": 2, "column": 11, "expected": ["']'", "value"], "message": "unexpected `,`"}"#
+        );
+    }
+
+    #[test]
+    fn report_escapes_message() {
+        let err = ParseError::new("expected \"\\\"\n\tgot \u{1}");
+        let report = ErrorReport::new("", &err);
+        assert_eq!(
+            report.to_json(),
+            r#"{"offset": null, "line": null, "column": null, "expected": [], "message": "expected \"\\\"\n\tgot \u0001"}"#
+        );
+    }
+
+    #[test]
+    fn finds_too_deep() {
+        let src = r#"{"a": [{"[\"{": []}]}"#;
+        assert_eq!(too_deep(src, 4), None);
+        assert_eq!(too_deep(src, 3), Some(src.rfind('[').unwrap()));
+        assert_eq!(too_deep("[] [] []", 1), None);
+        assert_eq!(too_deep("[]", 0), Some(0));
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let members = ["a", "b", "a"]
+            .into_iter()
+            .zip(1..)
+            .map(|(key, n)| (key.to_owned(), OrderedValue::Number(n.into())))
+            .collect::<OrderedMap>();
+        assert!(DuplicateKeys::Keep.check(&members).is_ok());
+        let err = DuplicateKeys::Reject.check(&members).unwrap_err();
+        assert_eq!(err.to_string(), r#"duplicate key "a""#);
+        assert!(DuplicateKeys::Reject.check(&members[..2].to_vec()).is_ok());
+
+        // The last of each key wins
+        let value = OrderedValue::Object(members).into_value();
+        let Value::Object(map) = value else {
+            panic!("expected an object, got {value:?}")
+        };
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], Value::Number(3.0));
+    }
+}
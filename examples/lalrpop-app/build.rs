@@ -1,5 +1,26 @@
 extern crate lalrpop;
 
 fn main() {
-    lalrpop::process_root().unwrap()
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let mut grammars = vec!["json.lalrpop"];
+    // Other modes are only generated on request, so they don't add to the build being benchmarked
+    if std::env::var_os("CARGO_FEATURE_SPANNED").is_some() {
+        grammars.push("spanned.lalrpop");
+    }
+    if std::env::var_os("CARGO_FEATURE_BORROWED").is_some() {
+        grammars.push("borrowed.lalrpop");
+    }
+    if std::env::var_os("CARGO_FEATURE_RAW_NUMBERS").is_some() {
+        grammars.push("raw_numbers.lalrpop");
+    }
+    if std::env::var_os("CARGO_FEATURE_ORDERED").is_some() {
+        grammars.push("ordered.lalrpop");
+    }
+    for grammar in grammars {
+        println!("cargo:rerun-if-changed={grammar}");
+        lalrpop::Configuration::new()
+            .set_out_dir(&out_dir)
+            .process_file(grammar)
+            .unwrap();
+    }
 }
@@ -0,0 +1,136 @@
+extern crate lalrpop_util;
+
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use json_model::escape::unescape;
+use json_model::{JsonParser, ParseError, Value};
+use lalrpop_util::ParseError as LalrpopError;
+
+lalrpop_util::lalrpop_mod!(pub json, "json.rs");
+#[cfg(feature = "spanned")]
+lalrpop_util::lalrpop_mod!(pub spanned, "spanned.rs");
+#[cfg(feature = "borrowed")]
+lalrpop_util::lalrpop_mod!(pub borrowed, "borrowed.rs");
+#[cfg(feature = "raw-numbers")]
+lalrpop_util::lalrpop_mod!(pub raw_numbers, "raw_numbers.rs");
+#[cfg(feature = "ordered")]
+lalrpop_util::lalrpop_mod!(pub ordered, "ordered.rs");
+
+pub struct Lalrpop;
+
+impl JsonParser for Lalrpop {
+    fn name(&self) -> &'static str {
+        "lalrpop"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        json::ValueParser::new()
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Lalrpop {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        spanned::ValueParser::new()
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Lalrpop {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        borrowed::ValueParser::new()
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Lalrpop {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        raw_numbers::ValueParser::new()
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Lalrpop {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        ordered::ValueParser::new()
+            .parse(keys, src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+/// Decode a `String` token found at `start`, quotes included
+fn string(start: usize, token: &str) -> Result<Cow<'_, str>, ParseError> {
+    unescape(&token[1..token.len() - 1]).map_err(|err| err.into_parse_error(start + 1))
+}
+
+pub fn parse_error<T: Display>(err: &LalrpopError<usize, T, ParseError>) -> ParseError {
+    let (offset, expected) = match err {
+        LalrpopError::InvalidToken { location } => (Some(*location), &[][..]),
+        LalrpopError::UnrecognizedEof { location, expected } => (Some(*location), &expected[..]),
+        LalrpopError::UnrecognizedToken {
+            token: (start, _, _),
+            expected,
+        } => (Some(*start), &expected[..]),
+        LalrpopError::ExtraToken {
+            token: (start, _, _),
+        } => (Some(*start), &[][..]),
+        LalrpopError::User { error } => return error.clone(),
+    };
+    ParseError::new(err.to_string())
+        .with_offset(offset)
+        .with_expected(expected.iter().cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use json_model::Map;
+
+    use super::*;
+
+    fn parse(src: &str) -> Result<Value, Option<usize>> {
+        Lalrpop.parse(src).map_err(|err| err.offset)
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            parse(r#""a\"b\\c\/\b\f\n\r\t\u00e9\uD83D\uDE10""#),
+            Ok(Value::String(
+                "a\"b\\c/\x08\x0C\n\r\t\u{e9}\u{1F610}".to_owned()
+            ))
+        );
+        assert_eq!(
+            parse(r#"{"\"": null}"#),
+            Ok(Value::Object(Map::from([("\"".to_owned(), Value::Null)])))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(parse(r#"["ok", "\x"]"#), Err(Some(8)));
+        assert_eq!(parse(r#""\uDE10""#), Err(Some(1)));
+        assert_eq!(parse(r#""\uD83D\n""#), Err(Some(1)));
+    }
+}
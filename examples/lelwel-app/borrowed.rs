@@ -0,0 +1,57 @@
+//! [`Cst::to_value`] borrowing strings from the input
+
+use json_model::{BorrowedMap, BorrowedValue};
+
+use crate::lexer::Token;
+use crate::parser::*;
+
+impl<'a> Cst<'a> {
+    pub fn to_borrowed_value(&self, node_ref: NodeRef) -> Option<BorrowedValue<'a>> {
+        match self.get(node_ref) {
+            Node::Rule(rule, _) => match rule {
+                Rule::File => self
+                    .children(node_ref)
+                    .find_map(|child_node_ref| self.to_borrowed_value(child_node_ref)),
+                Rule::Literal => self.to_borrowed_value(self.children(node_ref).next()?),
+                Rule::Array => Some(BorrowedValue::Array(
+                    self.children(node_ref)
+                        .filter_map(|child_node_ref| self.to_borrowed_value(child_node_ref))
+                        .collect(),
+                )),
+                Rule::Object => {
+                    let mut members = BorrowedMap::new();
+                    for mut member_node_refs in self
+                        .children(node_ref)
+                        .filter(|&child_node_ref| self.match_rule(child_node_ref, Rule::Member))
+                        .map(|child_node_ref| self.children(child_node_ref))
+                    {
+                        let Some(key) = member_node_refs
+                            .find_map(|member_node_ref| {
+                                self.match_token(member_node_ref, Token::String)
+                            })
+                            .and_then(|(key_str, _)| crate::string(key_str))
+                        else {
+                            continue;
+                        };
+                        let Some(val) = member_node_refs
+                            .find_map(|member_node_ref| self.to_borrowed_value(member_node_ref))
+                        else {
+                            continue;
+                        };
+                        members.insert(key, val);
+                    }
+                    Some(BorrowedValue::Object(members))
+                }
+                _ => None,
+            },
+            Node::Token(token, idx) => match token {
+                Token::String => Some(BorrowedValue::String(crate::string(self.span_text(idx))?)),
+                Token::Number => Some(BorrowedValue::Number(str::parse(self.span_text(idx)).ok()?)),
+                Token::True => Some(BorrowedValue::Bool(true)),
+                Token::False => Some(BorrowedValue::Bool(false)),
+                Token::Null => Some(BorrowedValue::Null),
+                _ => None,
+            },
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::parser::{Diagnostic, Span};
 use codespan_reporting::diagnostic::Label;
 use logos::{Lexer, Logos};
+use std::str::CharIndices;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum LexerError {
@@ -84,12 +85,22 @@ fn check_string(value: &str, span: &Span, diags: &mut Vec<Diagnostic>) {
             '\\' => match it.next() {
                 Some((_, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => {}
                 Some((i, 'u')) => {
-                    for j in 0..4 {
-                        if !it
-                            .next()
-                            .map(|(_, c)| c.is_ascii_hexdigit())
-                            .unwrap_or(false)
-                        {
+                    let lone = match hex4(&mut it) {
+                        Ok(0xD800..=0xDBFF) => {
+                            // Only consume the next escape if it is the low half
+                            let mut low = it.clone();
+                            let paired = matches!(
+                                (low.next(), low.next(), hex4(&mut low)),
+                                (Some((_, '\\')), Some((_, 'u')), Ok(0xDC00..=0xDFFF))
+                            );
+                            if paired {
+                                it = low;
+                            }
+                            !paired
+                        }
+                        Ok(0xDC00..=0xDFFF) => true,
+                        Ok(_) => false,
+                        Err(j) => {
                             diags.push(
                                 Diagnostic::error()
                                     .with_message("invalid unicode escape sequence")
@@ -98,8 +109,18 @@ fn check_string(value: &str, span: &Span, diags: &mut Vec<Diagnostic>) {
                                         span.start + i - 1..span.start + i + j + 1,
                                     )),
                             );
-                            break;
+                            false
                         }
+                    };
+                    if lone {
+                        diags.push(
+                            Diagnostic::error()
+                                .with_message("lone surrogate in unicode escape sequence")
+                                .with_label(Label::primary(
+                                    (),
+                                    span.start + i - 1..span.start + i + 5,
+                                )),
+                        );
                     }
                 }
                 Some((j, _)) => {
@@ -126,6 +147,18 @@ fn check_string(value: &str, span: &Span, diags: &mut Vec<Diagnostic>) {
     }
 }
 
+/// The value of the four hex digits of a `\\u` escape, or how many there were
+fn hex4(it: &mut CharIndices<'_>) -> Result<u32, usize> {
+    let mut value = 0;
+    for j in 0..4 {
+        match it.next().and_then(|(_, c)| c.to_digit(16)) {
+            Some(digit) => value = value * 16 + digit,
+            None => return Err(j),
+        }
+    }
+    Ok(value)
+}
+
 pub fn tokenize(source: &str, diags: &mut Vec<Diagnostic>) -> (Vec<Token>, Vec<Span>) {
     let lexer = Token::lexer(source);
     let mut tokens = vec![];
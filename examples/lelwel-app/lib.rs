@@ -0,0 +1,200 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+pub mod lexer;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use std::borrow::Cow;
+
+use codespan_reporting::diagnostic::{Diagnostic, Severity};
+use json_model::escape::unescape;
+use json_model::{JsonParser, Map, ParseError, Value};
+use lexer::Token;
+use parser::*;
+
+impl Cst<'_> {
+    pub fn to_value(&self, node_ref: NodeRef) -> Option<Value> {
+        match self.get(node_ref) {
+            Node::Rule(rule, _) => match rule {
+                Rule::File => self
+                    .children(node_ref)
+                    .find_map(|child_node_ref| self.to_value(child_node_ref)),
+                Rule::Literal => self.to_value(self.children(node_ref).next()?),
+                Rule::Array => Some(Value::Array(
+                    self.children(node_ref)
+                        .filter_map(|child_node_ref| self.to_value(child_node_ref))
+                        .collect(),
+                )),
+                Rule::Object => {
+                    let mut members = Map::new();
+                    for mut member_node_refs in self
+                        .children(node_ref)
+                        .filter(|&child_node_ref| self.match_rule(child_node_ref, Rule::Member))
+                        .map(|child_node_ref| self.children(child_node_ref))
+                    {
+                        let Some(key) = member_node_refs
+                            .find_map(|member_node_ref| {
+                                self.match_token(member_node_ref, Token::String)
+                            })
+                            .and_then(|(key_str, _)| string(key_str))
+                            .map(Cow::into_owned)
+                        else {
+                            continue;
+                        };
+                        let Some(val) = member_node_refs
+                            .find_map(|member_node_ref| self.to_value(member_node_ref))
+                        else {
+                            continue;
+                        };
+                        members.insert(key, val);
+                    }
+                    Some(Value::Object(members))
+                }
+                _ => None,
+            },
+            Node::Token(token, idx) => match token {
+                Token::String => Some(Value::String(string(self.span_text(idx))?.into_owned())),
+                Token::Number => Some(Value::Number(str::parse(self.span_text(idx)).ok()?)),
+                Token::True => Some(Value::Bool(true)),
+                Token::False => Some(Value::Bool(false)),
+                Token::Null => Some(Value::Null),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Decode a `String` token's text, whose escapes the lexer has already checked
+fn string(text: &str) -> Option<Cow<'_, str>> {
+    unescape(&text[1..text.len() - 1]).ok()
+}
+
+pub struct Lelwel;
+
+impl JsonParser for Lelwel {
+    fn name(&self) -> &'static str {
+        "lelwel"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        let mut diags = vec![];
+        let cst = Parser::new(src, &mut diags).parse(&mut diags);
+        if let Some(diag) = diags.iter().find(|d| d.severity == Severity::Error) {
+            return Err(parse_error(diag));
+        }
+        cst.to_value(NodeRef::ROOT)
+            .ok_or_else(|| ParseError::new("no parse result"))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Lelwel {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        let mut diags = vec![];
+        let cst = Parser::new(src, &mut diags).parse(&mut diags);
+        if let Some(diag) = diags.iter().find(|d| d.severity == Severity::Error) {
+            return Err(parse_error(diag));
+        }
+        cst.to_spanned_value(NodeRef::ROOT)
+            .ok_or_else(|| ParseError::new("no parse result"))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Lelwel {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        let mut diags = vec![];
+        let cst = Parser::new(src, &mut diags).parse(&mut diags);
+        if let Some(diag) = diags.iter().find(|d| d.severity == Severity::Error) {
+            return Err(parse_error(diag));
+        }
+        cst.to_borrowed_value(NodeRef::ROOT)
+            .ok_or_else(|| ParseError::new("no parse result"))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Lelwel {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        let mut diags = vec![];
+        let cst = Parser::new(src, &mut diags).parse(&mut diags);
+        if let Some(diag) = diags.iter().find(|d| d.severity == Severity::Error) {
+            return Err(parse_error(diag));
+        }
+        cst.to_raw_number_value(NodeRef::ROOT)
+            .ok_or_else(|| ParseError::new("no parse result"))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Lelwel {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        let mut diags = vec![];
+        let cst = Parser::new(src, &mut diags).parse(&mut diags);
+        if let Some(diag) = diags.iter().find(|d| d.severity == Severity::Error) {
+            return Err(parse_error(diag));
+        }
+        cst.to_ordered_value(NodeRef::ROOT, keys)?
+            .ok_or_else(|| ParseError::new("no parse result"))
+    }
+}
+
+/// The expected set is recovered from the generated `invalid syntax, expected ...` messages
+pub fn parse_error(diag: &Diagnostic<()>) -> ParseError {
+    let offset = diag.labels.first().map(|label| label.range.start);
+    let expected = diag
+        .message
+        .split_once("expected one of: ")
+        .or_else(|| diag.message.split_once("expected: "))
+        .map(|(_, expected)| expected.split(", ").collect::<Vec<_>>())
+        .unwrap_or_default();
+    ParseError::new(diag.message.clone())
+        .with_offset(offset)
+        .with_expected(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(src: &str) -> Result<Value, Option<usize>> {
+        Lelwel.parse(src).map_err(|err| err.offset)
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            parse(r#"{"a\"b\\c\/\b\f\n\r\t\u00e9\uD83D\uDE10": "\""}"#),
+            Ok(Value::Object(Map::from([(
+                "a\"b\\c/\x08\x0C\n\r\t\u{e9}\u{1F610}".to_owned(),
+                Value::String("\"".to_owned())
+            )])))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(parse(r#"["ok", "\x"]"#), Err(Some(8)));
+        assert_eq!(parse(r#"["\uDE10"]"#), Err(Some(2)));
+        assert_eq!(parse(r#"["\uD83D\n"]"#), Err(Some(2)));
+        assert_eq!(parse(r#"["\uD83D\uD83D\uDE10"]"#), Err(Some(2)));
+    }
+}
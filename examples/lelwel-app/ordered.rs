@@ -0,0 +1,74 @@
+//! [`Cst::to_value`] keeping object members in order
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue, ParseError};
+
+use crate::lexer::Token;
+use crate::parser::*;
+
+impl Cst<'_> {
+    /// Fails on a key repeated within an object if `keys` says to, at the object's `{`
+    pub fn to_ordered_value(
+        &self,
+        node_ref: NodeRef,
+        keys: DuplicateKeys,
+    ) -> Result<Option<OrderedValue>, ParseError> {
+        Ok(match self.get(node_ref) {
+            Node::Rule(rule, _) => match rule {
+                Rule::File | Rule::Literal => self
+                    .children(node_ref)
+                    .map(|child_node_ref| self.to_ordered_value(child_node_ref, keys))
+                    .find_map(Result::transpose)
+                    .transpose()?,
+                Rule::Array => Some(OrderedValue::Array(
+                    self.children(node_ref)
+                        .filter_map(|child_node_ref| {
+                            self.to_ordered_value(child_node_ref, keys).transpose()
+                        })
+                        .collect::<Result<_, _>>()?,
+                )),
+                Rule::Object => {
+                    let mut members = OrderedMap::new();
+                    for mut member_node_refs in self
+                        .children(node_ref)
+                        .filter(|&child_node_ref| self.match_rule(child_node_ref, Rule::Member))
+                        .map(|child_node_ref| self.children(child_node_ref))
+                    {
+                        let Some(key) = member_node_refs
+                            .find_map(|member_node_ref| {
+                                self.match_token(member_node_ref, Token::String)
+                            })
+                            .and_then(|(key_str, _)| crate::string(key_str))
+                        else {
+                            continue;
+                        };
+                        let Some(val) = member_node_refs
+                            .map(|member_node_ref| self.to_ordered_value(member_node_ref, keys))
+                            .find_map(Result::transpose)
+                            .transpose()?
+                        else {
+                            continue;
+                        };
+                        members.push((key.into_owned(), val));
+                    }
+                    keys.check(&members).map_err(|err| {
+                        ParseError::new(err.to_string()).with_offset(self.span(node_ref).start)
+                    })?;
+                    Some(OrderedValue::Object(members))
+                }
+                _ => None,
+            },
+            Node::Token(token, idx) => match token {
+                Token::String => {
+                    crate::string(self.span_text(idx)).map(|s| OrderedValue::String(s.into_owned()))
+                }
+                Token::Number => str::parse(self.span_text(idx))
+                    .ok()
+                    .map(OrderedValue::Number),
+                Token::True => Some(OrderedValue::Bool(true)),
+                Token::False => Some(OrderedValue::Bool(false)),
+                Token::Null => Some(OrderedValue::Null),
+                _ => None,
+            },
+        })
+    }
+}
@@ -0,0 +1,62 @@
+//! [`Cst::to_value`] keeping numbers as their text
+
+use std::borrow::Cow;
+
+use json_model::{RawNumberMap, RawNumberValue};
+
+use crate::lexer::Token;
+use crate::parser::*;
+
+impl<'a> Cst<'a> {
+    pub fn to_raw_number_value(&self, node_ref: NodeRef) -> Option<RawNumberValue<'a>> {
+        match self.get(node_ref) {
+            Node::Rule(rule, _) => match rule {
+                Rule::File => self
+                    .children(node_ref)
+                    .find_map(|child_node_ref| self.to_raw_number_value(child_node_ref)),
+                Rule::Literal => self.to_raw_number_value(self.children(node_ref).next()?),
+                Rule::Array => Some(RawNumberValue::Array(
+                    self.children(node_ref)
+                        .filter_map(|child_node_ref| self.to_raw_number_value(child_node_ref))
+                        .collect(),
+                )),
+                Rule::Object => {
+                    let mut members = RawNumberMap::new();
+                    for mut member_node_refs in self
+                        .children(node_ref)
+                        .filter(|&child_node_ref| self.match_rule(child_node_ref, Rule::Member))
+                        .map(|child_node_ref| self.children(child_node_ref))
+                    {
+                        let Some(key) = member_node_refs
+                            .find_map(|member_node_ref| {
+                                self.match_token(member_node_ref, Token::String)
+                            })
+                            .and_then(|(key_str, _)| crate::string(key_str))
+                            .map(Cow::into_owned)
+                        else {
+                            continue;
+                        };
+                        let Some(val) = member_node_refs
+                            .find_map(|member_node_ref| self.to_raw_number_value(member_node_ref))
+                        else {
+                            continue;
+                        };
+                        members.insert(key, val);
+                    }
+                    Some(RawNumberValue::Object(members))
+                }
+                _ => None,
+            },
+            Node::Token(token, idx) => match token {
+                Token::String => Some(RawNumberValue::String(
+                    crate::string(self.span_text(idx))?.into_owned(),
+                )),
+                Token::Number => Some(RawNumberValue::Number(self.span_text(idx))),
+                Token::True => Some(RawNumberValue::Bool(true)),
+                Token::False => Some(RawNumberValue::Bool(false)),
+                Token::Null => Some(RawNumberValue::Null),
+                _ => None,
+            },
+        }
+    }
+}
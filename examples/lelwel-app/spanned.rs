@@ -0,0 +1,64 @@
+//! [`Cst::to_value`] with a [`Span`](json_model::Span) on every value, from [`Cst::span`]
+
+use json_model::{Spanned, SpannedValue};
+
+use crate::lexer::Token;
+use crate::parser::*;
+
+impl Cst<'_> {
+    pub fn to_spanned_value(&self, node_ref: NodeRef) -> Option<Spanned<SpannedValue>> {
+        let value = match self.get(node_ref) {
+            Node::Rule(rule, _) => match rule {
+                Rule::File => {
+                    return self
+                        .children(node_ref)
+                        .find_map(|child_node_ref| self.to_spanned_value(child_node_ref))
+                }
+                Rule::Literal => return self.to_spanned_value(self.children(node_ref).next()?),
+                Rule::Array => SpannedValue::Array(
+                    self.children(node_ref)
+                        .filter_map(|child_node_ref| self.to_spanned_value(child_node_ref))
+                        .collect(),
+                ),
+                Rule::Object => {
+                    let mut members = Vec::new();
+                    for mut member_node_refs in self
+                        .children(node_ref)
+                        .filter(|&child_node_ref| self.match_rule(child_node_ref, Rule::Member))
+                        .map(|child_node_ref| self.children(child_node_ref))
+                    {
+                        let Some(key) = member_node_refs
+                            .find_map(|member_node_ref| {
+                                self.match_token(member_node_ref, Token::String)
+                            })
+                            .and_then(|(key_str, span)| {
+                                Some(Spanned::new(crate::string(key_str)?.into_owned(), span))
+                            })
+                        else {
+                            continue;
+                        };
+                        let Some(val) = member_node_refs
+                            .find_map(|member_node_ref| self.to_spanned_value(member_node_ref))
+                        else {
+                            continue;
+                        };
+                        members.push((key, val));
+                    }
+                    SpannedValue::Object(members)
+                }
+                _ => return None,
+            },
+            Node::Token(token, idx) => match token {
+                Token::String => {
+                    SpannedValue::String(crate::string(self.span_text(idx))?.into_owned())
+                }
+                Token::Number => SpannedValue::Number(str::parse(self.span_text(idx)).ok()?),
+                Token::True => SpannedValue::Bool(true),
+                Token::False => SpannedValue::Bool(false),
+                Token::Null => SpannedValue::Null,
+                _ => return None,
+            },
+        };
+        Some(Spanned::new(value, self.span(node_ref)))
+    }
+}
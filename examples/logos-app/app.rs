@@ -1,15 +1,60 @@
-mod parser;
-
-use std::{env, fs};
-
+use json_model::cli::{self, Args, ErrorFormat, Exit};
 use logos::Logos as _;
+use logos_app::parser;
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() {
-    let filename = env::args().nth(1).expect("Expected file argument");
-    let src = fs::read_to_string(&filename).expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            logos_app::Logos::new(args.max_depth).parse_spanned(src)
+        });
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            logos_app::Logos::new(args.max_depth).parse_borrowed(src)
+        });
+    }
 
-    let mut lexer = parser::Token::lexer(src.as_str());
-    match parser::parse_value(&mut lexer) {
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            logos_app::Logos::new(args.max_depth).parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            logos_app::Logos::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    let lexer = parser::Token::lexer(src.as_str());
+    match parser::parse_document(lexer, |lexer| parser::parse_value(lexer, args.max_depth)) {
         Ok(json) => {
             #[cfg(debug_assertions)]
             {
@@ -21,7 +66,14 @@ fn main() {
             }
         }
         Err((msg, span)) => {
-            eprintln!("{filename}:{span:?}: {msg}");
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{}:{span:?}: {msg}", args.path),
+                ErrorFormat::Json => cli::print_report(&src, &logos_app::parse_error((msg, span))),
+            }
+            Exit::Parse.exit();
         }
     }
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
@@ -0,0 +1,154 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{JsonParser, ParseError, Value, MAX_DEPTH};
+use logos::Logos as _;
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Logos {
+    pub max_depth: usize,
+}
+
+impl Logos {
+    pub const fn new(max_depth: usize) -> Self {
+        Logos { max_depth }
+    }
+}
+
+impl Default for Logos {
+    fn default() -> Self {
+        Logos::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Logos {
+    fn name(&self) -> &'static str {
+        "logos"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        parser::parse_document(parser::Token::lexer(src), |lexer| {
+            parser::parse_value(lexer, self.max_depth)
+        })
+        .map_err(parse_error)
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Logos {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        parser::parse_document(parser::Token::lexer(src), |lexer| {
+            spanned::parse_value(lexer, self.max_depth)
+        })
+        .map_err(parse_error)
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Logos {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        parser::parse_document(borrowed::Token::lexer(src), |lexer| {
+            borrowed::parse_value(lexer, self.max_depth)
+        })
+        .map_err(parse_error)
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Logos {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        parser::parse_document(raw_numbers::Token::lexer(src), |lexer| {
+            raw_numbers::parse_value(lexer, self.max_depth)
+        })
+        .map_err(parse_error)
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Logos {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        parser::parse_document(parser::Token::lexer(src), |lexer| {
+            ordered::parse_value(lexer, self.max_depth, keys)
+        })
+        .map_err(parse_error)
+    }
+}
+
+pub fn parse_error((msg, span): (String, logos::Span)) -> ParseError {
+    ParseError::new(msg).with_offset(span.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use json_model::Map;
+
+    use super::*;
+
+    fn parse(src: &str) -> Result<Value, Option<usize>> {
+        Logos::default().parse(src).map_err(|err| err.offset)
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            parse(r#"["a\"b\\c\/\b\f\n\r\t\u00e9\uD83D\uDE10"]"#),
+            Ok(Value::Array(vec![Value::String(
+                "a\"b\\c/\x08\x0C\n\r\t\u{e9}\u{1F610}".to_owned()
+            )]))
+        );
+        assert_eq!(
+            parse(r#"{"\"": null}"#),
+            Ok(Value::Object(Map::from([("\"".to_owned(), Value::Null)])))
+        );
+        assert_eq!(parse(r#""u0041""#), Ok(Value::String("u0041".to_owned())));
+    }
+
+    /// The lexer rejects the whole string
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(parse(r#"["ok", "\x"]"#), Err(Some(7)));
+        assert_eq!(parse(r#"["\uDE10"]"#), Err(Some(1)));
+        assert_eq!(parse(r#"["\uD83D\n"]"#), Err(Some(1)));
+    }
+
+    fn nested(depth: usize) -> String {
+        format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+    }
+
+    #[test]
+    fn limits_depth() {
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Err(Some(MAX_DEPTH)));
+        assert_eq!(parse(&nested(100_000)), Err(Some(MAX_DEPTH)));
+
+        let src = r#"{"a": [{"b": []}]}"#;
+        let mut lexer = parser::Token::lexer(src);
+        assert!(parser::parse_value(&mut lexer, 4).is_ok());
+        let mut lexer = parser::Token::lexer(src);
+        assert_eq!(
+            parser::parse_value(&mut lexer, 3),
+            Err(("nesting too deep".to_owned(), 13..14))
+        );
+    }
+}
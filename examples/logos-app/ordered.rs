@@ -0,0 +1,148 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+use logos::{Lexer, Span};
+
+use crate::parser::Token;
+
+type Error = (String, Span);
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parse a token stream into a JSON value keeping object members in order.
+///
+/// At most `depth` arrays and objects may be nested, and repeated keys are handled as `keys`
+/// says.
+pub fn parse_value(
+    lexer: &mut Lexer<'_, Token>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Result<OrderedValue> {
+    if let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::Bool(b)) => Ok(OrderedValue::Bool(b)),
+            Ok(Token::BraceOpen) => parse_object(lexer, depth, keys),
+            Ok(Token::BracketOpen) => parse_array(lexer, depth, keys),
+            Ok(Token::Null) => Ok(OrderedValue::Null),
+            Ok(Token::Number(n)) => Ok(OrderedValue::Number(n)),
+            Ok(Token::String(s)) => Ok(OrderedValue::String(s)),
+            _ => Err((
+                "unexpected token here (context: value)".to_owned(),
+                lexer.span(),
+            )),
+        }
+    } else {
+        Err(("empty values are not allowed".to_owned(), lexer.span()))
+    }
+}
+
+/// Parse a token stream into an array and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '[' was consumed.
+fn parse_array(
+    lexer: &mut Lexer<'_, Token>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Result<OrderedValue> {
+    let mut array = Vec::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_value = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::Bool(b)) if !awaits_comma => {
+                array.push(OrderedValue::Bool(b));
+                awaits_value = false;
+            }
+            Ok(Token::BraceOpen) if !awaits_comma => {
+                let object = parse_object(lexer, depth, keys)?;
+                array.push(object);
+                awaits_value = false;
+            }
+            Ok(Token::BracketOpen) if !awaits_comma => {
+                let sub_array = parse_array(lexer, depth, keys)?;
+                array.push(sub_array);
+                awaits_value = false;
+            }
+            Ok(Token::BracketClose) if !awaits_value => return Ok(OrderedValue::Array(array)),
+            Ok(Token::Comma) if awaits_comma => awaits_value = true,
+            Ok(Token::Null) if !awaits_comma => {
+                array.push(OrderedValue::Null);
+                awaits_value = false
+            }
+            Ok(Token::Number(n)) if !awaits_comma => {
+                array.push(OrderedValue::Number(n));
+                awaits_value = false;
+            }
+            Ok(Token::String(s)) if !awaits_comma => {
+                array.push(OrderedValue::String(s));
+                awaits_value = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: array)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_value;
+    }
+    Err(("unmatched opening bracket defined here".to_owned(), span))
+}
+
+/// Parse a token stream into an object and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '{' was consumed.
+fn parse_object(
+    lexer: &mut Lexer<'_, Token>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Result<OrderedValue> {
+    let mut map = OrderedMap::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_key = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::BraceClose) if !awaits_key => {
+                return match keys.check(&map) {
+                    Ok(()) => Ok(OrderedValue::Object(map)),
+                    Err(err) => Err((err.to_string(), span)),
+                };
+            }
+            Ok(Token::Comma) if awaits_comma => awaits_key = true,
+            Ok(Token::String(key)) if !awaits_comma => {
+                match lexer.next() {
+                    Some(Ok(Token::Colon)) => (),
+                    _ => {
+                        return Err((
+                            "unexpected token here, expecting ':'".to_owned(),
+                            lexer.span(),
+                        ))
+                    }
+                }
+                let value = parse_value(lexer, depth, keys)?;
+                map.push((key, value));
+                awaits_key = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: object)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_key;
+    }
+    Err(("unmatched opening brace defined here".to_owned(), span))
+}
@@ -12,9 +12,11 @@
 //!     cargo run --example json examples/example.json
 
 /* ANCHOR: all */
-use logos::{Lexer, Logos, Span};
+use std::borrow::Cow;
 
-use std::collections::HashMap;
+use json_model::escape::unescape;
+use json_model::{Map, Value};
+use logos::{Lexer, Logos, Span};
 
 type Error = (String, Span);
 
@@ -57,38 +59,25 @@ pub enum Token {
     #[regex(r"-?(?:0|[1-9]\d*)(?:\.\d+)?(?:[eE][+-]?\d+)?", |lex| lex.slice().parse::<f64>().unwrap())]
     Number(f64),
 
-    #[regex(r#""([^"\\]|\\["\\bnfrt]|u[a-fA-F0-9]{4})*""#, |lex| lex.slice().to_owned())]
+    #[regex(r#""([^"\\]|\\["\\/bfnrt]|\\u[a-fA-F0-9]{4})*""#, |lex| {
+        let s = lex.slice();
+        unescape(&s[1..s.len() - 1]).ok().map(Cow::into_owned)
+    })]
     String(String),
 }
 /* ANCHOR_END: tokens */
 
-/* ANCHOR: values */
-/// Represent any valid JSON value.
-#[derive(Debug)]
-pub enum Value {
-    /// null.
-    Null,
-    /// true or false.
-    Bool(bool),
-    /// Any floating point number.
-    Number(f64),
-    /// Any quoted string.
-    String(String),
-    /// An array of values
-    Array(Vec<Value>),
-    /// An dictionary mapping keys and values.
-    Object(HashMap<String, Value>),
-}
-/* ANCHOR_END: values */
-
 /* ANCHOR: value */
 /// Parse a token stream into a JSON value.
-pub fn parse_value(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
+///
+/// At most `depth` arrays and objects may be nested, so that deep input is rejected instead of
+/// overflowing the stack.
+pub fn parse_value(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<Value> {
     if let Some(token) = lexer.next() {
         match token {
             Ok(Token::Bool(b)) => Ok(Value::Bool(b)),
-            Ok(Token::BraceOpen) => parse_object(lexer),
-            Ok(Token::BracketOpen) => parse_array(lexer),
+            Ok(Token::BraceOpen) => parse_object(lexer, depth),
+            Ok(Token::BracketOpen) => parse_array(lexer, depth),
             Ok(Token::Null) => Ok(Value::Null),
             Ok(Token::Number(n)) => Ok(Value::Number(n)),
             Ok(Token::String(s)) => Ok(Value::String(s)),
@@ -108,9 +97,12 @@ pub fn parse_value(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
 /// a valid terminator is found.
 ///
 /// > NOTE: we assume '[' was consumed.
-fn parse_array(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
+fn parse_array(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<Value> {
     let mut array = Vec::new();
     let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
     let mut awaits_comma = false;
     let mut awaits_value = false;
 
@@ -121,12 +113,12 @@ fn parse_array(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
                 awaits_value = false;
             }
             Ok(Token::BraceOpen) if !awaits_comma => {
-                let object = parse_object(lexer)?;
+                let object = parse_object(lexer, depth)?;
                 array.push(object);
                 awaits_value = false;
             }
             Ok(Token::BracketOpen) if !awaits_comma => {
-                let sub_array = parse_array(lexer)?;
+                let sub_array = parse_array(lexer, depth)?;
                 array.push(sub_array);
                 awaits_value = false;
             }
@@ -162,9 +154,12 @@ fn parse_array(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
 /// a valid terminator is found.
 ///
 /// > NOTE: we assume '{' was consumed.
-fn parse_object(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
-    let mut map = HashMap::new();
+fn parse_object(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<Value> {
+    let mut map = Map::new();
     let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
     let mut awaits_comma = false;
     let mut awaits_key = false;
 
@@ -182,7 +177,7 @@ fn parse_object(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
                         ))
                     }
                 }
-                let value = parse_value(lexer)?;
+                let value = parse_value(lexer, depth)?;
                 map.insert(key, value);
                 awaits_key = false;
             }
@@ -198,3 +193,20 @@ fn parse_object(lexer: &mut Lexer<'_, Token>) -> Result<Value> {
     Err(("unmatched opening brace defined here".to_owned(), span))
 }
 /* ANCHOR_END: object */
+
+/// Parse one value from `lexer` with `parse_value`, rejecting any token after it.
+///
+/// Generic over the token type so that every mode shares the check.
+pub fn parse_document<'s, T: Logos<'s>, V>(
+    mut lexer: Lexer<'s, T>,
+    parse_value: impl FnOnce(&mut Lexer<'s, T>) -> Result<V>,
+) -> Result<V> {
+    let value = parse_value(&mut lexer)?;
+    match lexer.next() {
+        None => Ok(value),
+        Some(_) => Err((
+            "unexpected token here, expecting end of input".to_owned(),
+            lexer.span(),
+        )),
+    }
+}
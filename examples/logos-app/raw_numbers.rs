@@ -0,0 +1,173 @@
+//! [`parser`](crate::parser) keeping numbers as their text
+
+use std::borrow::Cow;
+
+use json_model::escape::unescape;
+use json_model::{RawNumberMap, RawNumberValue};
+use logos::{Lexer, Logos, Span};
+
+type Error = (String, Span);
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// [`parser::Token`](crate::parser::Token) with numbers borrowed from the source
+#[derive(Debug, Logos)]
+#[logos(skip r"[ \t\r\n\f]+")]
+pub enum Token<'s> {
+    #[token("false", |_| false)]
+    #[token("true", |_| true)]
+    Bool(bool),
+
+    #[token("{")]
+    BraceOpen,
+
+    #[token("}")]
+    BraceClose,
+
+    #[token("[")]
+    BracketOpen,
+
+    #[token("]")]
+    BracketClose,
+
+    #[token(":")]
+    Colon,
+
+    #[token(",")]
+    Comma,
+
+    #[token("null")]
+    Null,
+
+    #[regex(r"-?(?:0|[1-9]\d*)(?:\.\d+)?(?:[eE][+-]?\d+)?", |lex| lex.slice())]
+    Number(&'s str),
+
+    #[regex(r#""([^"\\]|\\["\\/bfnrt]|\\u[a-fA-F0-9]{4})*""#, |lex| {
+        let s = lex.slice();
+        unescape(&s[1..s.len() - 1]).ok().map(Cow::into_owned)
+    })]
+    String(String),
+}
+
+/// Parse a token stream into a JSON value keeping numbers as their text.
+///
+/// At most `depth` arrays and objects may be nested.
+pub fn parse_value<'s>(
+    lexer: &mut Lexer<'s, Token<'s>>,
+    depth: usize,
+) -> Result<RawNumberValue<'s>> {
+    if let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::Bool(b)) => Ok(RawNumberValue::Bool(b)),
+            Ok(Token::BraceOpen) => parse_object(lexer, depth),
+            Ok(Token::BracketOpen) => parse_array(lexer, depth),
+            Ok(Token::Null) => Ok(RawNumberValue::Null),
+            Ok(Token::Number(n)) => Ok(RawNumberValue::Number(n)),
+            Ok(Token::String(s)) => Ok(RawNumberValue::String(s)),
+            _ => Err((
+                "unexpected token here (context: value)".to_owned(),
+                lexer.span(),
+            )),
+        }
+    } else {
+        Err(("empty values are not allowed".to_owned(), lexer.span()))
+    }
+}
+
+/// Parse a token stream into an array and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '[' was consumed.
+fn parse_array<'s>(lexer: &mut Lexer<'s, Token<'s>>, depth: usize) -> Result<RawNumberValue<'s>> {
+    let mut array = Vec::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_value = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::Bool(b)) if !awaits_comma => {
+                array.push(RawNumberValue::Bool(b));
+                awaits_value = false;
+            }
+            Ok(Token::BraceOpen) if !awaits_comma => {
+                let object = parse_object(lexer, depth)?;
+                array.push(object);
+                awaits_value = false;
+            }
+            Ok(Token::BracketOpen) if !awaits_comma => {
+                let sub_array = parse_array(lexer, depth)?;
+                array.push(sub_array);
+                awaits_value = false;
+            }
+            Ok(Token::BracketClose) if !awaits_value => return Ok(RawNumberValue::Array(array)),
+            Ok(Token::Comma) if awaits_comma => awaits_value = true,
+            Ok(Token::Null) if !awaits_comma => {
+                array.push(RawNumberValue::Null);
+                awaits_value = false
+            }
+            Ok(Token::Number(n)) if !awaits_comma => {
+                array.push(RawNumberValue::Number(n));
+                awaits_value = false;
+            }
+            Ok(Token::String(s)) if !awaits_comma => {
+                array.push(RawNumberValue::String(s));
+                awaits_value = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: array)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_value;
+    }
+    Err(("unmatched opening bracket defined here".to_owned(), span))
+}
+
+/// Parse a token stream into an object and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '{' was consumed.
+fn parse_object<'s>(lexer: &mut Lexer<'s, Token<'s>>, depth: usize) -> Result<RawNumberValue<'s>> {
+    let mut map = RawNumberMap::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_key = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::BraceClose) if !awaits_key => return Ok(RawNumberValue::Object(map)),
+            Ok(Token::Comma) if awaits_comma => awaits_key = true,
+            Ok(Token::String(key)) if !awaits_comma => {
+                match lexer.next() {
+                    Some(Ok(Token::Colon)) => (),
+                    _ => {
+                        return Err((
+                            "unexpected token here, expecting ':'".to_owned(),
+                            lexer.span(),
+                        ))
+                    }
+                }
+                let value = parse_value(lexer, depth)?;
+                map.insert(key, value);
+                awaits_key = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: object)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_key;
+    }
+    Err(("unmatched opening brace defined here".to_owned(), span))
+}
@@ -0,0 +1,126 @@
+//! [`parser`](crate::parser) with a [`Span`] on every value
+
+use json_model::{Spanned, SpannedValue};
+use logos::{Lexer, Span};
+
+use crate::parser::Token;
+
+type Error = (String, Span);
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parse a token stream into a spanned JSON value.
+///
+/// At most `depth` arrays and objects may be nested.
+pub fn parse_value(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<Spanned<SpannedValue>> {
+    match lexer.next() {
+        Some(Ok(token)) => parse_token(lexer, token, "value", depth),
+        Some(Err(())) => Err((
+            "unexpected token here (context: value)".to_owned(),
+            lexer.span(),
+        )),
+        None => Err(("empty values are not allowed".to_owned(), lexer.span())),
+    }
+}
+
+/// Parse the value starting with `token`, which was just consumed.
+fn parse_token(
+    lexer: &mut Lexer<'_, Token>,
+    token: Token,
+    context: &str,
+    depth: usize,
+) -> Result<Spanned<SpannedValue>> {
+    let start = lexer.span().start;
+    let value = match token {
+        Token::Bool(b) => SpannedValue::Bool(b),
+        Token::BraceOpen => parse_object(lexer, depth)?,
+        Token::BracketOpen => parse_array(lexer, depth)?,
+        Token::Null => SpannedValue::Null,
+        Token::Number(n) => SpannedValue::Number(n),
+        Token::String(s) => SpannedValue::String(s),
+        _ => {
+            return Err((
+                format!("unexpected token here (context: {context})"),
+                lexer.span(),
+            ))
+        }
+    };
+    Ok(Spanned::new(value, start..lexer.span().end))
+}
+
+/// Parse a token stream into an array and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '[' was consumed.
+fn parse_array(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<SpannedValue> {
+    let mut array = Vec::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_value = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::BracketClose) if !awaits_value => return Ok(SpannedValue::Array(array)),
+            Ok(Token::Comma) if awaits_comma => awaits_value = true,
+            Ok(token) if !awaits_comma => {
+                array.push(parse_token(lexer, token, "array", depth)?);
+                awaits_value = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: array)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_value;
+    }
+    Err(("unmatched opening bracket defined here".to_owned(), span))
+}
+
+/// Parse a token stream into an object and return when
+/// a valid terminator is found.
+///
+/// > NOTE: we assume '{' was consumed.
+fn parse_object(lexer: &mut Lexer<'_, Token>, depth: usize) -> Result<SpannedValue> {
+    let mut members = Vec::new();
+    let span = lexer.span();
+    let Some(depth) = depth.checked_sub(1) else {
+        return Err(("nesting too deep".to_owned(), span));
+    };
+    let mut awaits_comma = false;
+    let mut awaits_key = false;
+
+    while let Some(token) = lexer.next() {
+        match token {
+            Ok(Token::BraceClose) if !awaits_key => return Ok(SpannedValue::Object(members)),
+            Ok(Token::Comma) if awaits_comma => awaits_key = true,
+            Ok(Token::String(key)) if !awaits_comma => {
+                let key = Spanned::new(key, lexer.span());
+                match lexer.next() {
+                    Some(Ok(Token::Colon)) => (),
+                    _ => {
+                        return Err((
+                            "unexpected token here, expecting ':'".to_owned(),
+                            lexer.span(),
+                        ))
+                    }
+                }
+                let value = parse_value(lexer, depth)?;
+                members.push((key, value));
+                awaits_key = false;
+            }
+            _ => {
+                return Err((
+                    "unexpected token here (context: object)".to_owned(),
+                    lexer.span(),
+                ))
+            }
+        }
+        awaits_comma = !awaits_key;
+    }
+    Err(("unmatched opening brace defined here".to_owned(), span))
+}
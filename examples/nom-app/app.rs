@@ -1,16 +1,49 @@
-mod parser;
-
-use std::{env, fs};
-
-use nom::Err;
-use nom_language::error::convert_error;
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+use nom_app::parser;
 use nom_language::error::VerboseError;
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
+
 fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            nom_app::Nom::new(args.max_depth).parse_spanned(src)
+        });
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            nom_app::Nom::new(args.max_depth).parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            nom_app::Nom::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
 
-    match parser::root::<VerboseError<&str>>(src.as_str()) {
+    match parser::root::<VerboseError<&str>>(args.max_depth, src.as_str()) {
         Ok(json) => {
             #[cfg(debug_assertions)]
             {
@@ -21,14 +54,16 @@ fn main() {
                 std::hint::black_box(json);
             }
         }
-        Err(Err::Error(err)) | Err(Err::Failure(err)) => {
-            let err = convert_error(src.as_str(), err);
-            eprintln!("{}", err);
-            std::process::exit(1);
-        }
         Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(1);
+            // The text output renders with `convert_error`, as it always has
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{}", nom_app::render_error(&src, &err)),
+                ErrorFormat::Json => cli::print_report(&src, &nom_app::parse_error(&src, &err)),
+            }
+            Exit::Parse.exit();
         }
     }
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
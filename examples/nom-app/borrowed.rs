@@ -0,0 +1,90 @@
+//! [`parser`](crate::parser) borrowing strings from the input
+
+use std::borrow::Cow;
+
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{cut, eof, map},
+    error::{context, ContextError, ParseError},
+    multi::separated_list0,
+    number::complete::double,
+    sequence::{preceded, separated_pair, terminated},
+    IResult, Parser,
+};
+
+use json_model::{BorrowedMap, BorrowedValue};
+
+use crate::parser::{boolean, null, open, sp, string};
+
+fn array<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, Vec<BorrowedValue<'a>>, E> {
+    context(
+        "array",
+        open('[', depth).flat_map(|depth| {
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), move |i| json_value(depth, i)),
+                preceded(sp, char(']')),
+            ))
+        }),
+    )
+    .parse(i)
+}
+
+fn key_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, (Cow<'a, str>, BorrowedValue<'a>), E> {
+    separated_pair(preceded(sp, string), cut(preceded(sp, char(':'))), |i| {
+        json_value(depth, i)
+    })
+    .parse(i)
+}
+
+fn hash<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, BorrowedMap<'a>, E> {
+    context(
+        "map",
+        open('{', depth).flat_map(|depth| {
+            cut(terminated(
+                map(
+                    separated_list0(preceded(sp, char(',')), move |i| key_value(depth, i)),
+                    |tuple_vec| tuple_vec.into_iter().collect(),
+                ),
+                preceded(sp, char('}')),
+            ))
+        }),
+    )
+    .parse(i)
+}
+
+fn json_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, BorrowedValue<'a>, E> {
+    preceded(
+        sp,
+        alt((
+            map(|i| hash(depth, i), BorrowedValue::Object),
+            map(|i| array(depth, i), BorrowedValue::Array),
+            map(string, BorrowedValue::String),
+            map(double, BorrowedValue::Number),
+            map(boolean, BorrowedValue::Bool),
+            map(null, |_| BorrowedValue::Null),
+        )),
+    )
+    .parse(i)
+}
+
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
+pub fn root<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    max_depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, BorrowedValue<'a>, E> {
+    terminated(|i| json_value(max_depth, i), terminated(sp, eof)).parse(i)
+}
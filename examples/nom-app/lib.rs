@@ -0,0 +1,141 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{ErrorReport, JsonParser, ParseError, Value, MAX_DEPTH};
+use nom::error::ErrorKind;
+use nom::{Err, Offset as _};
+use nom_language::error::convert_error;
+use nom_language::error::{VerboseError, VerboseErrorKind};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Nom {
+    pub max_depth: usize,
+}
+
+impl Nom {
+    pub const fn new(max_depth: usize) -> Self {
+        Nom { max_depth }
+    }
+}
+
+impl Default for Nom {
+    fn default() -> Self {
+        Nom::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Nom {
+    fn name(&self) -> &'static str {
+        "nom"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        match parser::root::<VerboseError<&str>>(self.max_depth, src) {
+            Ok((_, json)) => Ok(json),
+            Err(err) => Err(parse_error(src, &err)),
+        }
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Nom {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        match spanned::root::<VerboseError<&str>>(src, self.max_depth) {
+            Ok((_, json)) => Ok(json),
+            Err(err) => Err(parse_error(src, &err)),
+        }
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Nom {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        match borrowed::root::<VerboseError<&str>>(self.max_depth, src) {
+            Ok((_, json)) => Ok(json),
+            Err(err) => Err(parse_error(src, &err)),
+        }
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Nom {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        match ordered::root::<VerboseError<&str>>(keys, self.max_depth, src) {
+            Ok((_, json)) => Ok(json),
+            Err(err) => Err(parse_error(src, &err)),
+        }
+    }
+}
+
+/// The expected set is the characters `char` parsers were looking for at the error
+///
+/// Built from the [`VerboseError`] entries alone; [`render_error`] is the text form.
+pub fn parse_error(src: &str, err: &Err<VerboseError<&str>>) -> ParseError {
+    match err {
+        Err::Error(err) | Err::Failure(err) => {
+            let offset = err.errors.first().map(|(rest, _)| src.offset(rest));
+            let expected = err
+                .errors
+                .iter()
+                .filter(|(rest, _)| Some(src.offset(rest)) == offset)
+                .filter_map(|(_, kind)| match kind {
+                    VerboseErrorKind::Char(c) => Some(format!("'{c}'")),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let message = match err.errors.first() {
+                _ if !expected.is_empty() => format!("expected {}", expected.join(" or ")),
+                Some((_, VerboseErrorKind::Nom(ErrorKind::TooLarge))) => {
+                    "nesting too deep".to_owned()
+                }
+                Some((_, VerboseErrorKind::Context(context))) => format!("invalid {context}"),
+                Some((_, VerboseErrorKind::Nom(kind))) => kind.description().to_owned(),
+                _ => "invalid JSON".to_owned(),
+            };
+            ParseError::new(message)
+                .with_offset(offset)
+                .with_expected(expected)
+        }
+        Err::Incomplete(_) => ParseError::new(err.to_string()),
+    }
+}
+
+/// `convert_error`'s rendering of `err`, quoting the line of each entry with a caret under it
+///
+/// The caret is padded to its column with a format width, which panics past `u16::MAX`, so an
+/// error that far into a line falls back to [`parse_error`]'s message and position.
+pub fn render_error(src: &str, err: &Err<VerboseError<&str>>) -> String {
+    if let Err::Error(verbose) | Err::Failure(verbose) = err {
+        let fits = verbose.errors.iter().all(|(rest, _)| {
+            let offset = src.offset(rest);
+            let line_start = src[..offset].rfind('\n').map_or(0, |i| i + 1);
+            offset - line_start < usize::from(u16::MAX)
+        });
+        if fits {
+            return convert_error(src, verbose.clone());
+        }
+    }
+    let report = ErrorReport::new(src, &parse_error(src, err));
+    match (report.line, report.column) {
+        (Some(line), Some(column)) => {
+            format!("{} at line {line}, column {column}", report.message)
+        }
+        _ => report.message,
+    }
+}
@@ -0,0 +1,101 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{cut, eof, map},
+    error::{context, ContextError, ErrorKind, ParseError},
+    multi::separated_list0,
+    number::complete::double,
+    sequence::{preceded, separated_pair, terminated},
+    Err, IResult, Parser,
+};
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+
+use crate::parser::{boolean, null, open, sp, string};
+
+fn array<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    keys: DuplicateKeys,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, Vec<OrderedValue>, E> {
+    context(
+        "array",
+        open('[', depth).flat_map(|depth| {
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), move |i| json_value(keys, depth, i)),
+                preceded(sp, char(']')),
+            ))
+        }),
+    )
+    .parse(i)
+}
+
+fn key_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    keys: DuplicateKeys,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, (String, OrderedValue), E> {
+    separated_pair(
+        map(preceded(sp, string), String::from),
+        cut(preceded(sp, char(':'))),
+        |i| json_value(keys, depth, i),
+    )
+    .parse(i)
+}
+
+fn hash<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    keys: DuplicateKeys,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, OrderedMap, E> {
+    let (rest, members) = context(
+        "map",
+        open('{', depth).flat_map(|depth| {
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), move |i| key_value(keys, depth, i)),
+                preceded(sp, char('}')),
+            ))
+        }),
+    )
+    .parse(i)?;
+    match keys.check(&members) {
+        Ok(()) => Ok((rest, members)),
+        // Only known once the whole object is parsed, so point back at its `{`
+        Err(_) => Err(Err::Failure(E::add_context(
+            i,
+            "duplicate key",
+            E::from_error_kind(i, ErrorKind::Verify),
+        ))),
+    }
+}
+
+fn json_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    keys: DuplicateKeys,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, OrderedValue, E> {
+    preceded(
+        sp,
+        alt((
+            map(|i| hash(keys, depth, i), OrderedValue::Object),
+            map(|i| array(keys, depth, i), OrderedValue::Array),
+            map(string, |s| OrderedValue::String(String::from(s))),
+            map(double, OrderedValue::Number),
+            map(boolean, OrderedValue::Bool),
+            map(null, |_| OrderedValue::Null),
+        )),
+    )
+    .parse(i)
+}
+
+/// Repeated keys in an object are handled as `keys` says, and at most `max_depth` arrays and
+/// objects may nest
+pub fn root<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    keys: DuplicateKeys,
+    max_depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, OrderedValue, E> {
+    terminated(|i| json_value(keys, max_depth, i), terminated(sp, eof)).parse(i)
+}
@@ -1,38 +1,42 @@
 use nom::{
     branch::alt,
-    bytes::complete::{escaped, tag, take_while},
-    character::complete::{alphanumeric1 as alphanumeric, char, one_of},
-    combinator::{cut, map, opt, value},
-    error::{context, ContextError, ParseError},
+    bytes::complete::{escaped, tag, take_while, take_while1, take_while_m_n},
+    character::complete::{char, one_of},
+    combinator::{cut, eof, map, map_opt, success, value},
+    error::{context, ContextError, ErrorKind, ParseError},
     multi::separated_list0,
     number::complete::double,
-    sequence::{delimited, preceded, separated_pair, terminated},
-    IResult, Parser,
+    sequence::{preceded, separated_pair, terminated},
+    Err, IResult, Parser,
 };
-use std::collections::HashMap;
+use std::borrow::Cow;
 use std::str;
 
-#[derive(Debug, PartialEq)]
-pub enum JsonValue {
-    Null,
-    Str(String),
-    Boolean(bool),
-    Num(f64),
-    Array(Vec<JsonValue>),
-    Object(HashMap<String, JsonValue>),
-}
+use json_model::escape::unescape;
+use json_model::{Map, Value};
 
-fn sp<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, &'a str, E> {
+pub(crate) fn sp<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, &'a str, E> {
     let chars = " \t\r\n";
 
     take_while(move |c| chars.contains(c))(i)
 }
 
-fn parse_str<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, &'a str, E> {
-    escaped(alphanumeric, '\\', one_of("\"n\\"))(i)
+/// A string's contents, decoded once its escapes have been checked
+fn parse_str<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, Cow<'a, str>, E> {
+    let unicode = preceded(
+        char('u'),
+        take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()),
+    );
+    let escape = alt((value((), one_of("\"\\/bfnrt")), value((), unicode)));
+    // Control characters have to be escaped
+    let unescaped = take_while1(|c: char| c != '"' && c != '\\' && c >= ' ');
+    // `escaped` fails on an empty string
+    let raw = alt((escaped(unescaped, '\\', escape), success("")));
+    // Only fails on lone surrogates, which can't be stored in a `str`
+    map_opt(raw, |s| unescape(s).ok()).parse(i)
 }
 
-fn boolean<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, bool, E> {
+pub(crate) fn boolean<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, bool, E> {
     let parse_true = value(true, tag("true"));
 
     let parse_false = value(false, tag("false"));
@@ -40,13 +44,33 @@ fn boolean<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, bool,
     alt((parse_true, parse_false)).parse(input)
 }
 
-fn null<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
+pub(crate) fn null<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (), E> {
     value((), tag("null")).parse(input)
 }
 
-fn string<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+/// `bracket`, opening an array or object, and how many more levels may nest inside it
+///
+/// Fails once `depth` is used up, so that deep input is rejected instead of overflowing the stack.
+pub(crate) fn open<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    bracket: char,
+    depth: usize,
+) -> impl Parser<&'a str, Output = usize, Error = E> {
+    move |i: &'a str| {
+        let (rest, _) = char(bracket).parse(i)?;
+        match depth.checked_sub(1) {
+            Some(depth) => Ok((rest, depth)),
+            None => Err(Err::Failure(E::add_context(
+                i,
+                "nesting too deep",
+                E::from_error_kind(i, ErrorKind::TooLarge),
+            ))),
+        }
+    }
+}
+
+pub(crate) fn string<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     i: &'a str,
-) -> IResult<&'a str, &'a str, E> {
+) -> IResult<&'a str, Cow<'a, str>, E> {
     context(
         "string",
         preceded(char('\"'), cut(terminated(parse_str, char('\"')))),
@@ -55,42 +79,41 @@ fn string<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
 }
 
 fn array<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
     i: &'a str,
-) -> IResult<&'a str, Vec<JsonValue>, E> {
+) -> IResult<&'a str, Vec<Value>, E> {
     context(
         "array",
-        preceded(
-            char('['),
+        open('[', depth).flat_map(|depth| {
             cut(terminated(
-                separated_list0(preceded(sp, char(',')), json_value),
+                separated_list0(preceded(sp, char(',')), move |i| json_value(depth, i)),
                 preceded(sp, char(']')),
-            )),
-        ),
+            ))
+        }),
     )
     .parse(i)
 }
 
 fn key_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
     i: &'a str,
-) -> IResult<&'a str, (&'a str, JsonValue), E> {
-    separated_pair(
-        preceded(sp, string),
-        cut(preceded(sp, char(':'))),
-        json_value,
-    )
+) -> IResult<&'a str, (Cow<'a, str>, Value), E> {
+    separated_pair(preceded(sp, string), cut(preceded(sp, char(':'))), |i| {
+        json_value(depth, i)
+    })
     .parse(i)
 }
 
 fn hash<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
     i: &'a str,
-) -> IResult<&'a str, HashMap<String, JsonValue>, E> {
+) -> IResult<&'a str, Map, E> {
     context(
         "map",
-        preceded(
-            char('{'),
+        open('{', depth).flat_map(|depth| {
             cut(terminated(
                 map(
-                    separated_list0(preceded(sp, char(',')), key_value),
+                    separated_list0(preceded(sp, char(',')), move |i| key_value(depth, i)),
                     |tuple_vec| {
                         tuple_vec
                             .into_iter()
@@ -99,40 +122,35 @@ fn hash<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
                     },
                 ),
                 preceded(sp, char('}')),
-            )),
-        ),
+            ))
+        }),
     )
     .parse(i)
 }
 
 fn json_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    depth: usize,
     i: &'a str,
-) -> IResult<&'a str, JsonValue, E> {
+) -> IResult<&'a str, Value, E> {
     preceded(
         sp,
         alt((
-            map(hash, JsonValue::Object),
-            map(array, JsonValue::Array),
-            map(string, |s| JsonValue::Str(String::from(s))),
-            map(double, JsonValue::Num),
-            map(boolean, JsonValue::Boolean),
-            map(null, |_| JsonValue::Null),
+            map(|i| hash(depth, i), Value::Object),
+            map(|i| array(depth, i), Value::Array),
+            map(string, |s| Value::String(String::from(s))),
+            map(double, Value::Number),
+            map(boolean, Value::Bool),
+            map(null, |_| Value::Null),
         )),
     )
     .parse(i)
 }
 
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
 pub fn root<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    max_depth: usize,
     i: &'a str,
-) -> IResult<&'a str, JsonValue, E> {
-    delimited(
-        sp,
-        alt((
-            map(hash, JsonValue::Object),
-            map(array, JsonValue::Array),
-            map(null, |_| JsonValue::Null),
-        )),
-        opt(sp),
-    )
-    .parse(i)
+) -> IResult<&'a str, Value, E> {
+    terminated(|i| json_value(max_depth, i), terminated(sp, eof)).parse(i)
 }
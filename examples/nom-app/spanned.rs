@@ -0,0 +1,108 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value
+//!
+//! Spans come from [`consumed`] and [`Offset`] against the whole input, which each parser is
+//! handed as `src`.
+
+use nom::{
+    branch::alt,
+    character::complete::char,
+    combinator::{consumed, cut, eof, map},
+    error::{context, ContextError, ParseError},
+    multi::separated_list0,
+    number::complete::double,
+    sequence::{preceded, separated_pair, terminated},
+    IResult, Offset, Parser,
+};
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+
+use crate::parser::{boolean, null, open, sp, string};
+
+/// Wrap `parser`'s output in the span of what it consumed
+fn spanned<'a, O, E: ParseError<&'a str>>(
+    src: &'a str,
+    parser: impl Parser<&'a str, Output = O, Error = E>,
+) -> impl Parser<&'a str, Output = Spanned<O>, Error = E> {
+    map(consumed(parser), move |(text, value)| {
+        let start = src.offset(text);
+        Spanned::new(value, start..start + text.len())
+    })
+}
+
+fn array<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    src: &'a str,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, Vec<Spanned<SpannedValue>>, E> {
+    context(
+        "array",
+        open('[', depth).flat_map(|depth| {
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), move |i| json_value(src, depth, i)),
+                preceded(sp, char(']')),
+            ))
+        }),
+    )
+    .parse(i)
+}
+
+fn key_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    src: &'a str,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, SpannedMember, E> {
+    separated_pair(
+        preceded(sp, spanned(src, map(string, String::from))),
+        cut(preceded(sp, char(':'))),
+        |i| json_value(src, depth, i),
+    )
+    .parse(i)
+}
+
+fn hash<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    src: &'a str,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, Vec<SpannedMember>, E> {
+    context(
+        "map",
+        open('{', depth).flat_map(|depth| {
+            cut(terminated(
+                separated_list0(preceded(sp, char(',')), move |i| key_value(src, depth, i)),
+                preceded(sp, char('}')),
+            ))
+        }),
+    )
+    .parse(i)
+}
+
+fn json_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    src: &'a str,
+    depth: usize,
+    i: &'a str,
+) -> IResult<&'a str, Spanned<SpannedValue>, E> {
+    preceded(
+        sp,
+        spanned(
+            src,
+            alt((
+                map(|i| hash(src, depth, i), SpannedValue::Object),
+                map(|i| array(src, depth, i), SpannedValue::Array),
+                map(string, |s| SpannedValue::String(String::from(s))),
+                map(double, SpannedValue::Number),
+                map(boolean, SpannedValue::Bool),
+                map(null, |_| SpannedValue::Null),
+            )),
+        ),
+    )
+    .parse(i)
+}
+
+/// At most `max_depth` arrays and objects may nest, so that deep input is rejected instead of
+/// overflowing the stack
+pub fn root<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
+    src: &'a str,
+    max_depth: usize,
+) -> IResult<&'a str, Spanned<SpannedValue>, E> {
+    terminated(|i| json_value(src, max_depth, i), terminated(sp, eof)).parse(src)
+}
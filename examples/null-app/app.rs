@@ -1,8 +1,12 @@
-use std::{env, fs};
+use json_model::cli::{self, Args};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[]);
+    let src = cli::read_input(&args.path);
 
     #[cfg(debug_assertions)]
     {
@@ -13,5 +17,8 @@ fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
         std::hint::black_box(src);
     }
 
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
+
     Ok(())
 }
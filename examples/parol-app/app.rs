@@ -1,29 +1,90 @@
-mod grammar;
-mod grammar_trait;
-mod parser;
-
-use std::{env, fs};
-
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+use parol_app::{grammar, parser};
 use parol_runtime::Report;
 
 struct JSONErrorReporter;
 impl Report for JSONErrorReporter {}
 
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
+
 fn main() {
-    let path = env::args().nth(1).expect("Expected file argument");
-    let src = fs::read_to_string(&path).expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            parol_app::Parol::new(args.max_depth).parse_spanned(src)
+        });
+    }
 
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            parol_app::Parol::new(args.max_depth).parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            parol_app::Parol::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    if let Err(err) = json_model::check_depth(&src, args.max_depth) {
+        match args.errors {
+            ErrorFormat::Text => eprintln!("{}", err),
+            ErrorFormat::Json => cli::print_report(&src, &err),
+        }
+        Exit::Parse.exit();
+    }
     let mut json_grammar = grammar::Grammar::new();
-    match parser::parse(&src, &path, &mut json_grammar) {
-        Ok(_) => {
+    if let Err(err) = parser::parse(&src, &args.path, &mut json_grammar) {
+        match args.errors {
+            ErrorFormat::Text => {
+                let _ = JSONErrorReporter::report_error(&err, &args.path);
+            }
+            ErrorFormat::Json => cli::print_report(&src, &parol_app::parse_error(&err)),
+        }
+        Exit::Parse.exit();
+    }
+    // Escapes are only decoded when converting the tree
+    let json = json_grammar.json.expect("successful parse");
+    match json.try_into_value() {
+        Ok(json) => {
             #[cfg(debug_assertions)]
             {
-                println!("{}", json_grammar);
+                println!("{:#?}", json);
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                std::hint::black_box(json);
             }
         }
         Err(err) => {
-            let _ = JSONErrorReporter::report_error(&err, &path);
-            std::process::exit(1);
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{}", err),
+                ErrorFormat::Json => cli::print_report(&src, &err),
+            }
+            Exit::Parse.exit();
         }
-    };
+    }
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
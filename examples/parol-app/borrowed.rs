@@ -0,0 +1,53 @@
+//! [`Json`]'s tree converted into a [`BorrowedValue`]
+//!
+//! [`Token::text`](parol_runtime::Token::text) only lends its text for as long as the tree lives,
+//! so strings are sliced out of the input by their [`Location`](parol_runtime::Location)
+//! instead.
+
+use std::borrow::Cow;
+
+use json_model::escape::unescape;
+use json_model::{BorrowedMap, BorrowedValue, ParseError};
+
+use crate::grammar_trait::*;
+
+impl Json<'_> {
+    pub fn to_borrowed_value<'a>(&self, src: &'a str) -> Result<BorrowedValue<'a>, ParseError> {
+        self.value.to_borrowed_value(src)
+    }
+}
+
+impl Value<'_> {
+    fn to_borrowed_value<'a>(&self, src: &'a str) -> Result<BorrowedValue<'a>, ParseError> {
+        Ok(match self {
+            Value::String(v) => BorrowedValue::String(v.string.borrow_from(src)?),
+            Value::Number(v) => BorrowedValue::Number(v.number.try_into_f64()?),
+            Value::Object(v) => BorrowedValue::Object(match &v.object.object_suffix {
+                ObjectSuffix::PairObjectListRBrace(o) => std::iter::once(&*o.pair)
+                    .chain(o.object_list.iter().map(|e| &e.pair))
+                    .map(|p| Ok((p.string.borrow_from(src)?, p.value.to_borrowed_value(src)?)))
+                    .collect::<Result<_, _>>()?,
+                ObjectSuffix::RBrace(_) => BorrowedMap::new(),
+            }),
+            Value::Array(v) => BorrowedValue::Array(match &v.array.array_suffix {
+                ArraySuffix::ValueArrayListRBracket(a) => std::iter::once(&*a.value)
+                    .chain(a.array_list.iter().map(|e| &e.value))
+                    .map(|v| v.to_borrowed_value(src))
+                    .collect::<Result<_, _>>()?,
+                ArraySuffix::RBracket(_) => Vec::new(),
+            }),
+            Value::True(_) => BorrowedValue::Bool(true),
+            Value::False(_) => BorrowedValue::Bool(false),
+            Value::Null(_) => BorrowedValue::Null,
+        })
+    }
+}
+
+impl String<'_> {
+    /// The string's decoded text in `src`, only allocating if it has escapes
+    fn borrow_from<'a>(&self, src: &'a str) -> Result<Cow<'a, str>, ParseError> {
+        let location = &self.string.location;
+        let start = location.start as usize + 1;
+        unescape(&src[start..location.end as usize - 1]).map_err(|err| err.into_parse_error(start))
+    }
+}
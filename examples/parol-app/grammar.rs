@@ -1,5 +1,8 @@
 use crate::grammar_trait::*;
+use json_model::ParseError;
+use json_model::escape::unescape;
 use parol_runtime::Result;
+use std::borrow::Cow;
 use std::fmt::{Debug, Display, Error, Formatter};
 
 impl Display for Json<'_> {
@@ -76,6 +79,62 @@ impl Display for Pair<'_> {
     }
 }
 
+impl Json<'_> {
+    /// Convert into a [`json_model::Value`], decoding string escapes on the way
+    pub fn try_into_value(self) -> std::result::Result<json_model::Value, ParseError> {
+        self.value.try_into_value()
+    }
+}
+
+impl Value<'_> {
+    fn try_into_value(self) -> std::result::Result<json_model::Value, ParseError> {
+        Ok(match self {
+            Value::String(v) => json_model::Value::String(v.string.try_into_string()?),
+            Value::Number(v) => json_model::Value::Number(v.number.try_into_f64()?),
+            Value::Object(v) => json_model::Value::Object(match v.object.object_suffix {
+                ObjectSuffix::PairObjectListRBrace(o) => std::iter::once(*o.pair)
+                    .chain(o.object_list.into_iter().map(|e| e.pair))
+                    .map(|p| Ok((p.string.try_into_string()?, p.value.try_into_value()?)))
+                    .collect::<std::result::Result<_, _>>()?,
+                ObjectSuffix::RBrace(_) => json_model::Map::new(),
+            }),
+            Value::Array(v) => json_model::Value::Array(match v.array.array_suffix {
+                ArraySuffix::ValueArrayListRBracket(a) => std::iter::once(*a.value)
+                    .chain(a.array_list.into_iter().map(|e| e.value))
+                    .map(Value::try_into_value)
+                    .collect::<std::result::Result<_, _>>()?,
+                ArraySuffix::RBracket(_) => Vec::new(),
+            }),
+            Value::True(_) => json_model::Value::Bool(true),
+            Value::False(_) => json_model::Value::Bool(false),
+            Value::Null(_) => json_model::Value::Null,
+        })
+    }
+}
+
+impl Number<'_> {
+    /// The grammar lets an exponent go without digits, which `f64` doesn't
+    pub(crate) fn try_into_f64(&self) -> std::result::Result<f64, ParseError> {
+        self.number
+            .text()
+            .parse()
+            .map_err(|err: std::num::ParseFloatError| {
+                ParseError::new(format!("invalid number: {err}"))
+                    .with_offset(self.number.location.start as usize)
+            })
+    }
+}
+
+impl String<'_> {
+    pub(crate) fn try_into_string(self) -> std::result::Result<std::string::String, ParseError> {
+        let s = self.string.text();
+        let start = self.string.location.start as usize + 1;
+        unescape(&s[1..s.len() - 1])
+            .map(Cow::into_owned)
+            .map_err(|err| err.into_parse_error(start))
+    }
+}
+
 ///
 /// Data structure used to build up a json structure during parsing
 ///
@@ -0,0 +1,165 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+pub mod grammar;
+pub mod grammar_trait;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+#[cfg(feature = "spanned")]
+pub mod spanned_parser;
+#[cfg(feature = "spanned")]
+pub mod spanned_trait;
+
+use json_model::{JsonParser, MAX_DEPTH, ParseError, Value, check_depth};
+use parol_runtime::{ParolError, ParserError};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Parol {
+    pub max_depth: usize,
+}
+
+impl Parol {
+    pub const fn new(max_depth: usize) -> Self {
+        Parol { max_depth }
+    }
+}
+
+impl Default for Parol {
+    fn default() -> Self {
+        Parol::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Parol {
+    fn name(&self) -> &'static str {
+        "parol"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        check_depth(src, self.max_depth)?;
+        let mut json_grammar = grammar::Grammar::new();
+        parser::parse(src, "-", &mut json_grammar).map_err(|err| parse_error(&err))?;
+        json_grammar
+            .json
+            .ok_or_else(|| ParseError::new("no parse result"))?
+            .try_into_value()
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Parol {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        check_depth(src, self.max_depth)?;
+        let mut json_grammar = spanned::Grammar::new();
+        spanned_parser::parse(src, "-", &mut json_grammar).map_err(|err| parse_error(&err))?;
+        json_grammar
+            .json
+            .ok_or_else(|| ParseError::new("no parse result"))?
+            .try_into_spanned_value()
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Parol {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        check_depth(src, self.max_depth)?;
+        let mut json_grammar = grammar::Grammar::new();
+        parser::parse(src, "-", &mut json_grammar).map_err(|err| parse_error(&err))?;
+        json_grammar
+            .json
+            .ok_or_else(|| ParseError::new("no parse result"))?
+            .to_borrowed_value(src)
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Parol {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        check_depth(src, self.max_depth)?;
+        let mut json_grammar = grammar::Grammar::new();
+        parser::parse(src, "-", &mut json_grammar).map_err(|err| parse_error(&err))?;
+        json_grammar
+            .json
+            .ok_or_else(|| ParseError::new("no parse result"))?
+            .try_into_ordered_value(keys)
+    }
+}
+
+pub fn parse_error(err: &ParolError) -> ParseError {
+    let (offset, expected) = match err {
+        ParolError::ParserError(ParserError::SyntaxErrors { entries }) => match entries.first() {
+            Some(entry) => (
+                Some(entry.error_location.start as usize),
+                entry.expected_tokens.iter().cloned().collect(),
+            ),
+            None => (None, Vec::new()),
+        },
+        ParolError::ParserError(ParserError::UnprocessedInput { last_token, .. }) => {
+            (Some(last_token.end as usize), Vec::new())
+        }
+        _ => (None, Vec::new()),
+    };
+    ParseError::new(err.to_string())
+        .with_offset(offset)
+        .with_expected(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use json_model::Map;
+
+    use super::*;
+
+    fn parse(src: &str) -> Result<Value, Option<usize>> {
+        Parol::default().parse(src).map_err(|err| err.offset)
+    }
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(
+            parse(r#"["a\"b\\c\/\b\f\n\r\t\u00e9\uD83D\uDE10"]"#),
+            Ok(Value::Array(vec![Value::String(
+                "a\"b\\c/\x08\x0C\n\r\t\u{e9}\u{1F610}".to_owned()
+            )]))
+        );
+        assert_eq!(
+            parse(r#"{"\"": null}"#),
+            Ok(Value::Object(Map::from([("\"".to_owned(), Value::Null)])))
+        );
+        assert_eq!(
+            parse(r#"["\\", "x"]"#),
+            Ok(Value::Array(vec![
+                Value::String("\\".to_owned()),
+                Value::String("x".to_owned())
+            ]))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(parse(r#"["ok", "\x"]"#), Err(Some(8)));
+        assert_eq!(parse(r#""\uDE10""#), Err(Some(1)));
+        assert_eq!(parse(r#""\uD83D\n""#), Err(Some(1)));
+    }
+
+    #[test]
+    fn limits_depth() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(parse(&nested(MAX_DEPTH + 1)), Err(Some(MAX_DEPTH)));
+        assert_eq!(parse(&nested(100_000)), Err(Some(MAX_DEPTH)));
+    }
+}
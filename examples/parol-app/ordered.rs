@@ -0,0 +1,63 @@
+//! [`Json`]'s tree converted into an [`OrderedValue`]
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue, ParseError};
+
+use crate::grammar_trait::*;
+
+impl Json<'_> {
+    /// Fails on a key repeated within an object if `keys` says to, at its second occurrence
+    pub fn try_into_ordered_value(self, keys: DuplicateKeys) -> Result<OrderedValue, ParseError> {
+        self.value.try_into_ordered_value(keys)
+    }
+}
+
+impl Value<'_> {
+    fn try_into_ordered_value(self, keys: DuplicateKeys) -> Result<OrderedValue, ParseError> {
+        Ok(match self {
+            Value::String(v) => OrderedValue::String(v.string.try_into_string()?),
+            Value::Number(v) => OrderedValue::Number(v.number.try_into_f64()?),
+            Value::Object(v) => OrderedValue::Object(match v.object.object_suffix {
+                ObjectSuffix::PairObjectListRBrace(o) => {
+                    let pairs = std::iter::once(*o.pair)
+                        .chain(o.object_list.into_iter().map(|e| e.pair))
+                        .collect::<Vec<_>>();
+                    // The tree has no braces, so a duplicate is located by its key
+                    let starts = pairs
+                        .iter()
+                        .map(|p| p.string.string.location.start as usize)
+                        .collect::<Vec<_>>();
+                    let members = pairs
+                        .into_iter()
+                        .map(|p| {
+                            Ok((
+                                p.string.try_into_string()?,
+                                p.value.try_into_ordered_value(keys)?,
+                            ))
+                        })
+                        .collect::<Result<OrderedMap, _>>()?;
+                    keys.check(&members).map_err(|err| {
+                        let repeat = members
+                            .iter()
+                            .zip(starts)
+                            .filter(|((key, _), _)| *key == err.0)
+                            .nth(1)
+                            .map(|(_, start)| start);
+                        ParseError::new(err.to_string()).with_offset(repeat)
+                    })?;
+                    members
+                }
+                ObjectSuffix::RBrace(_) => OrderedMap::new(),
+            }),
+            Value::Array(v) => OrderedValue::Array(match v.array.array_suffix {
+                ArraySuffix::ValueArrayListRBracket(a) => std::iter::once(*a.value)
+                    .chain(a.array_list.into_iter().map(|e| e.value))
+                    .map(|v| v.try_into_ordered_value(keys))
+                    .collect::<Result<_, _>>()?,
+                ArraySuffix::RBracket(_) => Vec::new(),
+            }),
+            Value::True(_) => OrderedValue::Bool(true),
+            Value::False(_) => OrderedValue::Bool(false),
+            Value::Null(_) => OrderedValue::Null,
+        })
+    }
+}
@@ -0,0 +1,118 @@
+//! `spanned.par`'s tree converted into a [`Spanned<SpannedValue>`]
+//!
+//! `json.par` clips brackets and literals from its tree, leaving containers nothing to span, so
+//! `spanned.par` keeps them and gets a parser of its own.
+
+use std::borrow::Cow;
+
+use json_model::escape::unescape;
+use json_model::{ParseError, Span, Spanned, SpannedValue};
+use parol_runtime::{Result, Token};
+
+use crate::spanned_trait::*;
+
+/// Data structure used to build up a json structure during parsing
+#[derive(Debug, Default)]
+pub struct Grammar<'t> {
+    pub json: Option<Json<'t>>,
+}
+
+impl Grammar<'_> {
+    pub fn new() -> Self {
+        Grammar::default()
+    }
+}
+
+impl<'t> GrammarTrait<'t> for Grammar<'t> {
+    fn json(&mut self, arg: &Json<'t>) -> Result<()> {
+        self.json = Some(arg.clone());
+        Ok(())
+    }
+}
+
+/// From the start of `first` to the end of `last`
+fn span(first: &Token<'_>, last: &Token<'_>) -> Span {
+    first.location.start as usize..last.location.end as usize
+}
+
+impl Json<'_> {
+    /// Convert into a [`SpannedValue`], decoding string escapes on the way
+    pub fn try_into_spanned_value(self) -> std::result::Result<Spanned<SpannedValue>, ParseError> {
+        self.value.try_into_spanned_value()
+    }
+}
+
+impl Value<'_> {
+    fn try_into_spanned_value(self) -> std::result::Result<Spanned<SpannedValue>, ParseError> {
+        Ok(match self {
+            Value::String(v) => {
+                let span = span(&v.string.string, &v.string.string);
+                Spanned::new(SpannedValue::String(v.string.try_into_string()?), span)
+            }
+            Value::Number(v) => {
+                let span = span(&v.number.number, &v.number.number);
+                Spanned::new(SpannedValue::Number(v.number.try_into_f64()?), span)
+            }
+            Value::Object(v) => {
+                let (members, r_brace) = match v.object.object_suffix {
+                    ObjectSuffix::PairObjectListRBrace(o) => (
+                        std::iter::once(*o.pair)
+                            .chain(o.object_list.into_iter().map(|e| e.pair))
+                            .map(|p| {
+                                let span = span(&p.string.string, &p.string.string);
+                                let key = Spanned::new(p.string.try_into_string()?, span);
+                                Ok((key, p.value.try_into_spanned_value()?))
+                            })
+                            .collect::<std::result::Result<_, _>>()?,
+                        o.r_brace,
+                    ),
+                    ObjectSuffix::RBrace(o) => (Vec::new(), o.r_brace),
+                };
+                let span = span(&v.object.l_brace, &r_brace);
+                Spanned::new(SpannedValue::Object(members), span)
+            }
+            Value::Array(v) => {
+                let (values, r_bracket) = match v.array.array_suffix {
+                    ArraySuffix::ValueArrayListRBracket(a) => (
+                        std::iter::once(*a.value)
+                            .chain(a.array_list.into_iter().map(|e| e.value))
+                            .map(Value::try_into_spanned_value)
+                            .collect::<std::result::Result<_, _>>()?,
+                        a.r_bracket,
+                    ),
+                    ArraySuffix::RBracket(a) => (Vec::new(), a.r_bracket),
+                };
+                let span = span(&v.array.l_bracket, &r_bracket);
+                Spanned::new(SpannedValue::Array(values), span)
+            }
+            Value::True(v) => Spanned::new(SpannedValue::Bool(true), span(&v.r#true, &v.r#true)),
+            Value::False(v) => {
+                Spanned::new(SpannedValue::Bool(false), span(&v.r#false, &v.r#false))
+            }
+            Value::Null(v) => Spanned::new(SpannedValue::Null, span(&v.null, &v.null)),
+        })
+    }
+}
+
+impl Number<'_> {
+    /// The grammar lets an exponent go without digits, which `f64` doesn't
+    fn try_into_f64(&self) -> std::result::Result<f64, ParseError> {
+        self.number
+            .text()
+            .parse()
+            .map_err(|err: std::num::ParseFloatError| {
+                ParseError::new(format!("invalid number: {err}"))
+                    .with_offset(self.number.location.start as usize)
+            })
+    }
+}
+
+impl String<'_> {
+    fn try_into_string(self) -> std::result::Result<std::string::String, ParseError> {
+        let s = self.string.text();
+        let start = self.string.location.start as usize + 1;
+        unescape(&s[1..s.len() - 1])
+            .map(Cow::into_owned)
+            .map_err(|err| err.into_parse_error(start))
+    }
+}
@@ -1,19 +1,19 @@
-#[test]
-fn codegen() {
+fn codegen(name: &str, grammar: &str, parser: &str, module: &str) {
     use snapbox::Data;
     use snapbox::assert_data_eq;
 
     let tmp_dir = env!("CARGO_TARGET_TMPDIR");
     let mut output_dir = std::path::PathBuf::from(tmp_dir);
-    output_dir.push("parol");
+    output_dir.push(name);
     std::fs::create_dir_all(&output_dir).unwrap();
 
     let expected_root = std::path::Path::new(".");
 
     let mut builder = parol::build::Builder::with_explicit_output_dir(&output_dir);
-    builder.grammar_file("json.par");
-    builder.parser_output_file("parser.rs");
-    builder.actions_output_file("grammar_trait.rs");
+    builder.grammar_file(grammar);
+    builder.parser_output_file(parser);
+    builder.actions_output_file(format!("{module}_trait.rs"));
+    builder.user_trait_module_name(module);
     builder.trim_parse_tree();
     builder.minimize_boxed_types();
     builder.generate_parser().unwrap();
@@ -27,3 +27,19 @@ fn codegen() {
         assert_data_eq!(actual, Data::read_from(&expected_path, None).raw());
     }
 }
+
+#[test]
+fn json() {
+    codegen("parol", "json.par", "parser.rs", "grammar");
+}
+
+/// Keeps the brackets and literals that `json.par` clips, for their spans
+#[test]
+fn spanned() {
+    codegen(
+        "parol-spanned",
+        "spanned.par",
+        "spanned_parser.rs",
+        "spanned",
+    );
+}
@@ -1,12 +1,58 @@
-mod parser;
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+use peg_app::parser;
 
-use std::{env, fs};
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
 
-    match parser::parser::json(&src) {
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            peg_app::Peg::new(args.max_depth).parse_spanned(src)
+        });
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            peg_app::Peg::new(args.max_depth).parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            peg_app::Peg::new(args.max_depth).parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            peg_app::Peg::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    match parser::parser::json(&src, args.max_depth) {
         Ok(json) => {
             #[cfg(debug_assertions)]
             {
@@ -18,8 +64,14 @@ fn main() {
             }
         }
         Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(1);
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{}", err),
+                ErrorFormat::Json => cli::print_report(&src, &peg_app::parse_error(&err)),
+            }
+            Exit::Parse.exit();
         }
     };
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
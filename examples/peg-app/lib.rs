@@ -0,0 +1,86 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{JsonParser, ParseError, Value, MAX_DEPTH};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Peg {
+    pub max_depth: usize,
+}
+
+impl Peg {
+    pub const fn new(max_depth: usize) -> Self {
+        Peg { max_depth }
+    }
+}
+
+impl Default for Peg {
+    fn default() -> Self {
+        Peg::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Peg {
+    fn name(&self) -> &'static str {
+        "peg"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        parser::parser::json(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Peg {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        spanned::parser::json(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Peg {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        borrowed::parser::json(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Peg {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        raw_numbers::parser::json(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Peg {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        ordered::parser::json(src, keys, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+pub fn parse_error(err: &peg::error::ParseError<peg::str::LineCol>) -> ParseError {
+    ParseError::new(err.to_string())
+        .with_offset(err.location.offset)
+        .with_expected(err.expected.tokens())
+}
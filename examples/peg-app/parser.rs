@@ -1,48 +1,46 @@
-use std::{borrow::Cow, collections::HashMap, str::FromStr};
-
-#[derive(Debug, PartialEq, Clone)]
-pub enum JsonValue {
-    Null,
-    Boolean(bool),
-    Str(String),
-    Num(f64),
-    Array(Vec<JsonValue>),
-    Object(HashMap<String, JsonValue>),
-}
+use std::{borrow::Cow, str::FromStr};
+
+use json_model::Value;
 
 peg::parser!(pub grammar parser() for str {
 
-pub rule json() -> JsonValue
-    = _ value:value() _ { value }
+/// At most `max_depth` arrays and objects may nest
+pub rule json(max_depth: usize) -> Value
+    = _ value:value(max_depth) _ { value }
 
 rule _() = [' ' | '\t' | '\r' | '\n']*
 rule value_separator() = _ "," _
 
-rule value() -> JsonValue
-    = boolean() / null() / object() / array() / number() / string()
+rule value(depth: usize) -> Value
+    = boolean() / null() / object(depth) / array(depth) / number() / string()
 
-rule null() -> JsonValue
-    = "null" { JsonValue::Null }
+rule null() -> Value
+    = "null" { Value::Null }
 
-rule boolean() -> JsonValue
-    = "true" { JsonValue::Boolean(true) }
-    / "false" { JsonValue::Boolean(false) }
+rule boolean() -> Value
+    = "true" { Value::Bool(true) }
+    / "false" { Value::Bool(false) }
 
-rule object() -> JsonValue
-    = "{" _ elements:(member() ** value_separator()) _ "}" {
-        JsonValue::Object(elements.into_iter().collect())
+rule object(depth: usize) -> Value
+    = "{" depth:nest(depth) _ elements:(member(depth) ** value_separator()) _ "}" {
+        Value::Object(elements.into_iter().collect())
     }
 
-rule member() -> (String, JsonValue)
-    = key:raw_string() _ ":" _ value:value() { (key, value) }
+rule member(depth: usize) -> (String, Value)
+    = key:raw_string() _ ":" _ value:value(depth) { (key, value) }
 
-rule array() -> JsonValue
-    = "[" _ elements:(value() ** value_separator()) _ "]" {
-        JsonValue::Array(elements)
+rule array(depth: usize) -> Value
+    = "[" depth:nest(depth) _ elements:(value(depth) ** value_separator()) _ "]" {
+        Value::Array(elements)
     }
 
-rule string() -> JsonValue
-    = value:raw_string() { JsonValue::Str(value) }
+/// One level less of `depth` inside an array or object, failing once it is used up so that deep
+/// input is rejected instead of overflowing the stack
+rule nest(depth: usize) -> usize
+    = {? depth.checked_sub(1).ok_or("shallower nesting") }
+
+rule string() -> Value
+    = value:raw_string() { Value::String(value) }
 
 rule raw_string() -> String
     = "\"" slices:string_slice()* "\"" { slices.concat() }
@@ -70,17 +68,29 @@ rule string_escape_char() -> char
     / "n" { '\n' }
     / "r" { '\r' }
     / "t" { '\t' }
-    / "u" digits:$(hex_digit()*<4>) { ?
-        let value = u16::from_str_radix(digits, 16).unwrap();
-        char::from_u32(value.into()).ok_or("invalid unicode escape")
+    / "u" value:unicode_escape() { value }
+
+/// A `\u` escape's code point, combining a surrogate pair; lone surrogates are rejected, as
+/// they can't be stored in a `str`
+rule unicode_escape() -> char
+    = high:hex4() "\\u" low:hex4() { ?
+        if (0xD800..0xDC00).contains(&high) && (0xDC00..0xE000).contains(&low) {
+            Ok(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).unwrap())
+        } else {
+            Err("surrogate pair")
+        }
     }
+    / value:hex4() { ? char::from_u32(value).ok_or("non-surrogate code point") }
+
+rule hex4() -> u32
+    = digits:$(hex_digit()*<4>) { u32::from_str_radix(digits, 16).unwrap() }
 
 rule hex_digit()
     = ['0'..='9' | 'a'..='f' | 'A'..='F']
 
-rule number() -> JsonValue
-    = "-"? value:$(int() frac()? exp()?) { ?
-        Ok(JsonValue::Num(f64::from_str(value).map_err(|_| "invalid number")?))
+rule number() -> Value
+    = value:$("-"? int() frac()? exp()?) { ?
+        Ok(Value::Number(f64::from_str(value).map_err(|_| "invalid number")?))
     }
 
 rule int()
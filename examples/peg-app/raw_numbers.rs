@@ -0,0 +1,104 @@
+//! [`parser`](crate::parser) keeping numbers as their text
+
+use std::borrow::Cow;
+
+use json_model::RawNumberValue;
+
+peg::parser!(pub grammar parser() for str {
+
+/// At most `max_depth` arrays and objects may nest
+pub rule json(max_depth: usize) -> RawNumberValue<'input>
+    = _ value:value(max_depth) _ { value }
+
+rule _() = [' ' | '\t' | '\r' | '\n']*
+rule value_separator() = _ "," _
+
+rule value(depth: usize) -> RawNumberValue<'input>
+    = boolean() / null() / object(depth) / array(depth) / number() / string()
+
+rule null() -> RawNumberValue<'input>
+    = "null" { RawNumberValue::Null }
+
+rule boolean() -> RawNumberValue<'input>
+    = "true" { RawNumberValue::Bool(true) }
+    / "false" { RawNumberValue::Bool(false) }
+
+rule object(depth: usize) -> RawNumberValue<'input>
+    = "{" depth:nest(depth) _ elements:(member(depth) ** value_separator()) _ "}" {
+        RawNumberValue::Object(elements.into_iter().collect())
+    }
+
+rule member(depth: usize) -> (String, RawNumberValue<'input>)
+    = key:raw_string() _ ":" _ value:value(depth) { (key, value) }
+
+rule array(depth: usize) -> RawNumberValue<'input>
+    = "[" depth:nest(depth) _ elements:(value(depth) ** value_separator()) _ "]" {
+        RawNumberValue::Array(elements)
+    }
+
+/// One level less of `depth` inside an array or object, failing once it is used up so that deep
+/// input is rejected instead of overflowing the stack
+rule nest(depth: usize) -> usize
+    = {? depth.checked_sub(1).ok_or("shallower nesting") }
+
+rule string() -> RawNumberValue<'input>
+    = value:raw_string() { RawNumberValue::String(value) }
+
+rule raw_string() -> String
+    = "\"" slices:string_slice()* "\"" { slices.concat() }
+
+/// A substring of same-kind (escaped or unescaped) characters
+rule string_slice() -> Cow<'input, str>
+    = value:string_characters() { Cow::Borrowed(value) }
+    / value:string_escapes() { Cow::Owned(value.into_iter().collect()) }
+
+/// A substring of unescaped characters
+rule string_characters() -> &'input str
+    = $([^ '\"' | '\\']+)
+
+/// A substring of escaped characters
+rule string_escapes() -> Vec<char>
+    = ("\\" value:string_escape_char() { value })+
+
+/// Handles a single escape
+rule string_escape_char() -> char
+    = "\"" { '"' }
+    / "\\" { '\\' }
+    / "/"  { '/' }
+    / "b" { '\x08' }
+    / "f" { '\x0C' }
+    / "n" { '\n' }
+    / "r" { '\r' }
+    / "t" { '\t' }
+    / "u" value:unicode_escape() { value }
+
+/// A `\u` escape's code point, combining a surrogate pair; lone surrogates are rejected, as
+/// they can't be stored in a `str`
+rule unicode_escape() -> char
+    = high:hex4() "\\u" low:hex4() { ?
+        if (0xD800..0xDC00).contains(&high) && (0xDC00..0xE000).contains(&low) {
+            Ok(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).unwrap())
+        } else {
+            Err("surrogate pair")
+        }
+    }
+    / value:hex4() { ? char::from_u32(value).ok_or("non-surrogate code point") }
+
+rule hex4() -> u32
+    = digits:$(hex_digit()*<4>) { u32::from_str_radix(digits, 16).unwrap() }
+
+rule hex_digit()
+    = ['0'..='9' | 'a'..='f' | 'A'..='F']
+
+rule number() -> RawNumberValue<'input>
+    = value:$("-"? int() frac()? exp()?) { RawNumberValue::Number(value) }
+
+rule int()
+    = ['0'] / ['1'..='9']['0'..='9']*
+
+rule exp()
+    = ("e" / "E") ("-" / "+")? ['0'..='9']*<1,>
+
+rule frac()
+    = "." ['0'..='9']*<1,>
+});
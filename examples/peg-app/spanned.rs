@@ -0,0 +1,109 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value
+
+use std::{borrow::Cow, str::FromStr};
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+
+peg::parser!(pub grammar parser() for str {
+
+/// At most `max_depth` arrays and objects may nest
+pub rule json(max_depth: usize) -> Spanned<SpannedValue>
+    = _ value:value(max_depth) _ { value }
+
+rule _() = [' ' | '\t' | '\r' | '\n']*
+rule value_separator() = _ "," _
+
+rule value(depth: usize) -> Spanned<SpannedValue>
+    = start:position!() value:(boolean() / null() / object(depth) / array(depth) / number() / string())
+      end:position!() { Spanned::new(value, start..end) }
+
+rule null() -> SpannedValue
+    = "null" { SpannedValue::Null }
+
+rule boolean() -> SpannedValue
+    = "true" { SpannedValue::Bool(true) }
+    / "false" { SpannedValue::Bool(false) }
+
+rule object(depth: usize) -> SpannedValue
+    = "{" depth:nest(depth) _ elements:(member(depth) ** value_separator()) _ "}" {
+        SpannedValue::Object(elements)
+    }
+
+rule member(depth: usize) -> SpannedMember
+    = start:position!() key:raw_string() end:position!() _ ":" _ value:value(depth) {
+        (Spanned::new(key, start..end), value)
+    }
+
+rule array(depth: usize) -> SpannedValue
+    = "[" depth:nest(depth) _ elements:(value(depth) ** value_separator()) _ "]" {
+        SpannedValue::Array(elements)
+    }
+
+/// One level less of `depth` inside an array or object, failing once it is used up so that deep
+/// input is rejected instead of overflowing the stack
+rule nest(depth: usize) -> usize
+    = {? depth.checked_sub(1).ok_or("shallower nesting") }
+
+rule string() -> SpannedValue
+    = value:raw_string() { SpannedValue::String(value) }
+
+rule raw_string() -> String
+    = "\"" slices:string_slice()* "\"" { slices.concat() }
+
+/// A substring of same-kind (escaped or unescaped) characters
+rule string_slice() -> Cow<'input, str>
+    = value:string_characters() { Cow::Borrowed(value) }
+    / value:string_escapes() { Cow::Owned(value.into_iter().collect()) }
+
+/// A substring of unescaped characters
+rule string_characters() -> &'input str
+    = $([^ '\"' | '\\']+)
+
+/// A substring of escaped characters
+rule string_escapes() -> Vec<char>
+    = ("\\" value:string_escape_char() { value })+
+
+/// Handles a single escape
+rule string_escape_char() -> char
+    = "\"" { '"' }
+    / "\\" { '\\' }
+    / "/"  { '/' }
+    / "b" { '\x08' }
+    / "f" { '\x0C' }
+    / "n" { '\n' }
+    / "r" { '\r' }
+    / "t" { '\t' }
+    / "u" value:unicode_escape() { value }
+
+/// A `\u` escape's code point, combining a surrogate pair; lone surrogates are rejected, as
+/// they can't be stored in a `str`
+rule unicode_escape() -> char
+    = high:hex4() "\\u" low:hex4() { ?
+        if (0xD800..0xDC00).contains(&high) && (0xDC00..0xE000).contains(&low) {
+            Ok(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).unwrap())
+        } else {
+            Err("surrogate pair")
+        }
+    }
+    / value:hex4() { ? char::from_u32(value).ok_or("non-surrogate code point") }
+
+rule hex4() -> u32
+    = digits:$(hex_digit()*<4>) { u32::from_str_radix(digits, 16).unwrap() }
+
+rule hex_digit()
+    = ['0'..='9' | 'a'..='f' | 'A'..='F']
+
+rule number() -> SpannedValue
+    = value:$("-"? int() frac()? exp()?) { ?
+        Ok(SpannedValue::Number(f64::from_str(value).map_err(|_| "invalid number")?))
+    }
+
+rule int()
+    = ['0'] / ['1'..='9']['0'..='9']*
+
+rule exp()
+    = ("e" / "E") ("-" / "+")? ['0'..='9']*<1,>
+
+rule frac()
+    = "." ['0'..='9']*<1,>
+});
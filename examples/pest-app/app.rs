@@ -7,19 +7,65 @@
 // option. All files in the project carrying such notice may not be copied,
 // modified, or distributed except according to those terms.
 
-mod parser;
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+use pest_app::parser;
 
-use std::{env, fs};
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "spanned")]
+        cli::Mode::Spanned,
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
 
-    match parser::parse_json_file(&src) {
+    #[cfg(feature = "spanned")]
+    if args.mode == cli::Mode::Spanned {
+        use json_model::SpannedParser;
+        cli::run(&args, &src, |src| {
+            pest_app::Pest::new(args.max_depth).parse_spanned(src)
+        });
+    }
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            pest_app::Pest::new(args.max_depth).parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            pest_app::Pest::new(args.max_depth).parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            pest_app::Pest::new(args.max_depth).parse_ordered(src, keys)
+        });
+    }
+
+    match parser::parse_json_file(&src, args.max_depth) {
         Ok(json) => {
             #[cfg(debug_assertions)]
             {
-                println!("{:#?}", json);
+                println!("{:#?}", json_model::IntoValue::into_value(json));
             }
             #[cfg(not(debug_assertions))]
             {
@@ -27,8 +73,14 @@ fn main() {
             }
         }
         Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(1);
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{}", err),
+                ErrorFormat::Json => cli::print_report(&src, &pest_app::parse_error(&err)),
+            }
+            Exit::Parse.exit();
         }
     };
+
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
 }
@@ -0,0 +1,48 @@
+//! [`parse_json_file`](crate::parser::parse_json_file) building a [`BorrowedValue`] instead of
+//! its own [`Json`](crate::parser::Json)
+
+use json_model::BorrowedValue;
+use pest::error::Error;
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{check_depth, unescape, JSONParser, Rule};
+
+pub fn parse_json_file(input: &str, max_depth: usize) -> Result<BorrowedValue<'_>, Error<Rule>> {
+    check_depth(input, max_depth)?;
+    let json = JSONParser::parse(Rule::json, input)?.next().unwrap();
+
+    fn parse_value(pair: Pair<Rule>) -> Result<BorrowedValue, Error<Rule>> {
+        Ok(match pair.as_rule() {
+            Rule::object => BorrowedValue::Object(
+                pair.into_inner()
+                    .map(|pair| {
+                        let mut inner_rules = pair.into_inner();
+                        let name =
+                            unescape(&inner_rules.next().unwrap().into_inner().next().unwrap())?;
+                        let value = parse_value(inner_rules.next().unwrap())?;
+                        Ok((name, value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::array => BorrowedValue::Array(
+                pair.into_inner()
+                    .map(parse_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::string => BorrowedValue::String(unescape(&pair.into_inner().next().unwrap())?),
+            Rule::number => BorrowedValue::Number(pair.as_str().parse().unwrap()),
+            Rule::boolean => BorrowedValue::Bool(pair.as_str().parse().unwrap()),
+            Rule::null => BorrowedValue::Null,
+            Rule::json
+            | Rule::EOI
+            | Rule::pair
+            | Rule::value
+            | Rule::inner
+            | Rule::char
+            | Rule::WHITESPACE => unreachable!(),
+        })
+    }
+
+    parse_value(json)
+}
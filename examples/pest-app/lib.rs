@@ -0,0 +1,109 @@
+// pest. The Elegant Parser
+// Copyright (c) 2018 Dragoș Tiselice
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. All files in the project carrying such notice may not be copied,
+// modified, or distributed except according to those terms.
+
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{IntoValue as _, JsonParser, ParseError, Value, MAX_DEPTH};
+use pest::error::{ErrorVariant, InputLocation};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Pest {
+    pub max_depth: usize,
+}
+
+impl Pest {
+    pub const fn new(max_depth: usize) -> Self {
+        Pest { max_depth }
+    }
+}
+
+impl Default for Pest {
+    fn default() -> Self {
+        Pest::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Pest {
+    fn name(&self) -> &'static str {
+        "pest"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        parser::parse_json_file(src, self.max_depth)
+            .map(|json| json.into_value())
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Pest {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        spanned::parse_json_file(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Pest {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        borrowed::parse_json_file(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Pest {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        raw_numbers::parse_json_file(src, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Pest {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        ordered::parse_json_file(src, keys, self.max_depth).map_err(|err| parse_error(&err))
+    }
+}
+
+/// The expected set is the rules that could have matched
+pub fn parse_error(err: &pest::error::Error<parser::Rule>) -> ParseError {
+    let offset = match err.location {
+        InputLocation::Pos(pos) => pos,
+        InputLocation::Span((start, _)) => start,
+    };
+    let expected = match &err.variant {
+        ErrorVariant::ParsingError { positives, .. } => {
+            positives.iter().map(|rule| format!("{rule:?}")).collect()
+        }
+        ErrorVariant::CustomError { .. } => Vec::new(),
+    };
+    ParseError::new(err.to_string())
+        .with_offset(offset)
+        .with_expected(expected)
+}
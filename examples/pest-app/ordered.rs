@@ -0,0 +1,66 @@
+//! [`parse_json_file`](crate::parser::parse_json_file) building an [`OrderedValue`] instead of
+//! its own [`Json`](crate::parser::Json)
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{check_depth, unescape, JSONParser, Rule};
+
+/// Repeated keys in an object are handled as `keys` says
+pub fn parse_json_file(
+    input: &str,
+    keys: DuplicateKeys,
+    max_depth: usize,
+) -> Result<OrderedValue, Error<Rule>> {
+    check_depth(input, max_depth)?;
+    let json = JSONParser::parse(Rule::json, input)?.next().unwrap();
+
+    fn parse_value(pair: Pair<Rule>, keys: DuplicateKeys) -> Result<OrderedValue, Error<Rule>> {
+        Ok(match pair.as_rule() {
+            Rule::object => {
+                let span = pair.as_span();
+                let members = pair
+                    .into_inner()
+                    .map(|pair| {
+                        let mut inner_rules = pair.into_inner();
+                        let name =
+                            unescape(&inner_rules.next().unwrap().into_inner().next().unwrap())?;
+                        let value = parse_value(inner_rules.next().unwrap(), keys)?;
+                        Ok((name.into_owned(), value))
+                    })
+                    .collect::<Result<OrderedMap, _>>()?;
+                keys.check(&members).map_err(|err| {
+                    Error::new_from_span(
+                        ErrorVariant::CustomError {
+                            message: err.to_string(),
+                        },
+                        span,
+                    )
+                })?;
+                OrderedValue::Object(members)
+            }
+            Rule::array => OrderedValue::Array(
+                pair.into_inner()
+                    .map(|pair| parse_value(pair, keys))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::string => {
+                OrderedValue::String(unescape(&pair.into_inner().next().unwrap())?.into_owned())
+            }
+            Rule::number => OrderedValue::Number(pair.as_str().parse().unwrap()),
+            Rule::boolean => OrderedValue::Bool(pair.as_str().parse().unwrap()),
+            Rule::null => OrderedValue::Null,
+            Rule::json
+            | Rule::EOI
+            | Rule::pair
+            | Rule::value
+            | Rule::inner
+            | Rule::char
+            | Rule::WHITESPACE => unreachable!(),
+        })
+    }
+
+    parse_value(json, keys)
+}
@@ -7,51 +7,98 @@
 // option. All files in the project carrying such notice may not be copied,
 // modified, or distributed except according to those terms.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use pest::error::Error;
-use pest::Parser;
+use json_model::{escape, IntoValue};
+use pest::error::{Error, ErrorVariant};
+use pest::iterators::Pair;
+use pest::{Parser, Position};
 use pest_derive::Parser;
 
 #[derive(Parser)]
 #[grammar = "json.pest"]
-struct JSONParser;
+pub(crate) struct JSONParser;
 
 #[derive(Debug, PartialEq)]
 pub enum Json<'i> {
     Null,
     Bool(bool),
     Number(f64),
-    String(&'i str),
+    String(Cow<'i, str>),
     Array(Vec<Json<'i>>),
-    Object(HashMap<&'i str, Json<'i>>),
+    Object(HashMap<Cow<'i, str>, Json<'i>>),
 }
 
-pub fn parse_json_file(input: &str) -> Result<Json<'_>, Error<Rule>> {
-    use pest::iterators::Pair;
+impl IntoValue for Json<'_> {
+    fn into_value(self) -> json_model::Value {
+        use json_model::Value;
 
+        match self {
+            Json::Null => Value::Null,
+            Json::Bool(b) => Value::Bool(b),
+            Json::Number(n) => Value::Number(n),
+            Json::String(s) => Value::String(s.into_owned()),
+            Json::Array(a) => Value::Array(a.into_iter().map(IntoValue::into_value).collect()),
+            Json::Object(o) => Value::Object(
+                o.into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Decode the escapes in a `string`'s `inner` pair
+pub(crate) fn unescape<'i>(inner: &Pair<'i, Rule>) -> Result<Cow<'i, str>, Error<Rule>> {
+    escape::unescape(inner.as_str()).map_err(|err| {
+        let pos = inner.as_span().start() + err.offset;
+        Error::new_from_pos(
+            ErrorVariant::CustomError {
+                message: err.to_string(),
+            },
+            Position::new(inner.get_input(), pos).unwrap(),
+        )
+    })
+}
+
+/// Reject `input` if it nests more than `max_depth` deep, before pest's recursion overflows the
+/// stack on it
+pub(crate) fn check_depth(input: &str, max_depth: usize) -> Result<(), Error<Rule>> {
+    match json_model::too_deep(input, max_depth) {
+        Some(pos) => Err(Error::new_from_pos(
+            ErrorVariant::CustomError {
+                message: "nesting too deep".to_owned(),
+            },
+            Position::new(input, pos).unwrap(),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn parse_json_file(input: &str, max_depth: usize) -> Result<Json<'_>, Error<Rule>> {
+    check_depth(input, max_depth)?;
     let json = JSONParser::parse(Rule::json, input)?.next().unwrap();
 
-    fn parse_value(pair: Pair<Rule>) -> Json {
-        match pair.as_rule() {
+    fn parse_value(pair: Pair<Rule>) -> Result<Json, Error<Rule>> {
+        Ok(match pair.as_rule() {
             Rule::object => Json::Object(
                 pair.into_inner()
                     .map(|pair| {
                         let mut inner_rules = pair.into_inner();
-                        let name = inner_rules
-                            .next()
-                            .unwrap()
-                            .into_inner()
-                            .next()
-                            .unwrap()
-                            .as_str();
-                        let value = parse_value(inner_rules.next().unwrap());
-                        (name, value)
+                        let name =
+                            unescape(&inner_rules.next().unwrap().into_inner().next().unwrap())?;
+                        let value = parse_value(inner_rules.next().unwrap())?;
+                        Ok((name, value))
                     })
-                    .collect(),
+                    .collect::<Result<_, _>>()?,
             ),
-            Rule::array => Json::Array(pair.into_inner().map(parse_value).collect()),
-            Rule::string => Json::String(pair.into_inner().next().unwrap().as_str()),
+            Rule::array => Json::Array(
+                pair.into_inner()
+                    .map(parse_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::string => Json::String(unescape(&pair.into_inner().next().unwrap())?),
             Rule::number => Json::Number(pair.as_str().parse().unwrap()),
             Rule::boolean => Json::Bool(pair.as_str().parse().unwrap()),
             Rule::null => Json::Null,
@@ -62,8 +109,8 @@ pub fn parse_json_file(input: &str) -> Result<Json<'_>, Error<Rule>> {
             | Rule::inner
             | Rule::char
             | Rule::WHITESPACE => unreachable!(),
-        }
+        })
     }
 
-    Ok(parse_value(json))
+    parse_value(json)
 }
@@ -0,0 +1,51 @@
+//! [`parse_json_file`](crate::parser::parse_json_file) building a [`RawNumberValue`], with each
+//! number kept as its text, instead of its own [`Json`](crate::parser::Json)
+
+use json_model::RawNumberValue;
+use pest::error::Error;
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{check_depth, unescape, JSONParser, Rule};
+
+pub fn parse_json_file(input: &str, max_depth: usize) -> Result<RawNumberValue<'_>, Error<Rule>> {
+    check_depth(input, max_depth)?;
+    let json = JSONParser::parse(Rule::json, input)?.next().unwrap();
+
+    fn parse_value(pair: Pair<Rule>) -> Result<RawNumberValue, Error<Rule>> {
+        Ok(match pair.as_rule() {
+            Rule::object => RawNumberValue::Object(
+                pair.into_inner()
+                    .map(|pair| {
+                        let mut inner_rules = pair.into_inner();
+                        let name =
+                            unescape(&inner_rules.next().unwrap().into_inner().next().unwrap())?
+                                .into_owned();
+                        let value = parse_value(inner_rules.next().unwrap())?;
+                        Ok((name, value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::array => RawNumberValue::Array(
+                pair.into_inner()
+                    .map(parse_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::string => {
+                RawNumberValue::String(unescape(&pair.into_inner().next().unwrap())?.into_owned())
+            }
+            Rule::number => RawNumberValue::Number(pair.as_str()),
+            Rule::boolean => RawNumberValue::Bool(pair.as_str().parse().unwrap()),
+            Rule::null => RawNumberValue::Null,
+            Rule::json
+            | Rule::EOI
+            | Rule::pair
+            | Rule::value
+            | Rule::inner
+            | Rule::char
+            | Rule::WHITESPACE => unreachable!(),
+        })
+    }
+
+    parse_value(json)
+}
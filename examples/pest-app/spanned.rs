@@ -0,0 +1,61 @@
+//! [`parse_json_file`](crate::parser::parse_json_file) with a [`Span`](json_model::Span) on
+//! every value, from [`Pair::as_span`]
+
+use json_model::{Spanned, SpannedValue};
+use pest::error::Error;
+use pest::iterators::Pair;
+use pest::Parser;
+
+use crate::parser::{check_depth, unescape, JSONParser, Rule};
+
+pub fn parse_json_file(
+    input: &str,
+    max_depth: usize,
+) -> Result<Spanned<SpannedValue>, Error<Rule>> {
+    check_depth(input, max_depth)?;
+    let json = JSONParser::parse(Rule::json, input)?.next().unwrap();
+
+    fn spanned<T>(pair: &Pair<Rule>, value: T) -> Spanned<T> {
+        let span = pair.as_span();
+        Spanned::new(value, span.start()..span.end())
+    }
+
+    fn parse_value(pair: Pair<Rule>) -> Result<Spanned<SpannedValue>, Error<Rule>> {
+        let value = match pair.as_rule() {
+            Rule::object => SpannedValue::Object(
+                pair.clone()
+                    .into_inner()
+                    .map(|pair| {
+                        let mut inner_rules = pair.into_inner();
+                        let string = inner_rules.next().unwrap();
+                        let name = unescape(&string.clone().into_inner().next().unwrap())?;
+                        let value = parse_value(inner_rules.next().unwrap())?;
+                        Ok((spanned(&string, name.into_owned()), value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::array => SpannedValue::Array(
+                pair.clone()
+                    .into_inner()
+                    .map(parse_value)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Rule::string => SpannedValue::String(
+                unescape(&pair.clone().into_inner().next().unwrap())?.into_owned(),
+            ),
+            Rule::number => SpannedValue::Number(pair.as_str().parse().unwrap()),
+            Rule::boolean => SpannedValue::Bool(pair.as_str().parse().unwrap()),
+            Rule::null => SpannedValue::Null,
+            Rule::json
+            | Rule::EOI
+            | Rule::pair
+            | Rule::value
+            | Rule::inner
+            | Rule::char
+            | Rule::WHITESPACE => unreachable!(),
+        };
+        Ok(spanned(&pair, value))
+    }
+
+    parse_value(json)
+}
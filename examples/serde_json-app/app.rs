@@ -1,22 +1,70 @@
-use std::{env, fs};
+use json_model::cli::{self, Args, ErrorFormat, Exit};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: count_alloc::CountingAlloc = count_alloc::CountingAlloc;
 
 fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-    let src = fs::read_to_string(env::args().nth(1).expect("Expected file argument"))
-        .expect("Failed to read file");
+    let args = Args::from_env(&[
+        #[cfg(feature = "borrowed")]
+        cli::Mode::Borrowed,
+        #[cfg(feature = "raw-numbers")]
+        cli::Mode::RawNumbers,
+        #[cfg(feature = "ordered")]
+        cli::Mode::Ordered,
+        #[cfg(feature = "ordered")]
+        cli::Mode::UniqueKeys,
+    ]);
+    let src = cli::read_input(&args.path);
+
+    #[cfg(feature = "borrowed")]
+    if args.mode == cli::Mode::Borrowed {
+        use json_model::BorrowedParser;
+        cli::run(&args, &src, |src| {
+            serde_json_app::SerdeJson.parse_borrowed(src)
+        });
+    }
+
+    #[cfg(feature = "raw-numbers")]
+    if args.mode == cli::Mode::RawNumbers {
+        use json_model::RawNumberParser;
+        cli::run(&args, &src, |src| {
+            serde_json_app::SerdeJson.parse_raw_numbers(src)
+        });
+    }
+
+    #[cfg(feature = "ordered")]
+    if let Some(keys) = args.mode.duplicate_keys() {
+        use json_model::OrderedParser;
+        cli::run(&args, &src, |src| {
+            serde_json_app::SerdeJson.parse_ordered(src, keys)
+        });
+    }
 
     match serde_json::from_str::<serde_json::Value>(&src) {
         Ok(json) => {
             #[cfg(debug_assertions)]
             {
-                println!("{:#?}", json);
+                println!("{:#?}", json_model::IntoValue::into_value(json));
             }
             #[cfg(not(debug_assertions))]
             {
                 std::hint::black_box(json);
             }
         }
-        Err(e) => eprintln!("{e}"),
+        Err(e) => {
+            match args.errors {
+                ErrorFormat::Text => eprintln!("{e}"),
+                ErrorFormat::Json => {
+                    cli::print_report(&src, &serde_json_app::parse_error(&src, &e))
+                }
+            }
+            Exit::Parse.exit();
+        }
     }
 
+    #[cfg(feature = "count-alloc")]
+    count_alloc::report();
+
     Ok(())
 }
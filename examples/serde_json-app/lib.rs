@@ -0,0 +1,86 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+
+use json_model::{IntoValue as _, JsonParser, ParseError, Value};
+
+pub struct SerdeJson;
+
+impl JsonParser for SerdeJson {
+    fn name(&self) -> &'static str {
+        "serde_json"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        serde_json::from_str::<serde_json::Value>(src)
+            .map(|json| json.into_value())
+            .map_err(|err| parse_error(src, &err))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for SerdeJson {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        serde_json::from_str::<borrowed::Borrowed<'_>>(src)
+            .map(|json| json.0)
+            .map_err(|err| parse_error(src, &err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for SerdeJson {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        use serde::de::DeserializeSeed as _;
+
+        let mut deserializer = serde_json::Deserializer::from_str(src);
+        ordered::Ordered(keys)
+            .deserialize(&mut deserializer)
+            .and_then(|json| deserializer.end().map(|()| json))
+            .map_err(|err| parse_error(src, &err))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for SerdeJson {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        use serde::de::DeserializeSeed as _;
+
+        let mut deserializer = serde_json::Deserializer::from_str(src);
+        raw_numbers::Raw {
+            depth: json_model::MAX_DEPTH,
+        }
+        .deserialize(&mut deserializer)
+        .and_then(|json| deserializer.end().map(|()| json))
+        .map_err(|err| parse_error(src, &err))
+    }
+}
+
+pub fn parse_error(src: &str, err: &serde_json::Error) -> ParseError {
+    ParseError::new(err.to_string()).with_offset(offset(src, err.line(), err.column()))
+}
+
+/// Convert `serde_json`'s 1-based line and column into a byte offset
+fn offset(src: &str, line: usize, column: usize) -> Option<usize> {
+    if line == 0 {
+        return None;
+    }
+    let line_start = src
+        .split_inclusive('\n')
+        .take(line - 1)
+        .map(str::len)
+        .sum::<usize>();
+    Some(line_start + column.saturating_sub(1))
+}
@@ -0,0 +1,73 @@
+//! Deserialize an [`OrderedValue`] straight from `serde_json`, which hands over object members in
+//! input order
+
+use std::fmt;
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+use serde::de::{DeserializeSeed, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+
+/// Seed for an [`OrderedValue`], with repeated keys in an object handled as the [`DuplicateKeys`]
+/// says
+#[derive(Copy, Clone)]
+pub struct Ordered(pub DuplicateKeys);
+
+impl<'de> DeserializeSeed<'de> for Ordered {
+    type Value = OrderedValue;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Ordered {
+    type Value = OrderedValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Number(v as f64))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Number(v as f64))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(OrderedValue::Number(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(OrderedValue::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(OrderedValue::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(self)? {
+            array.push(value);
+        }
+        Ok(OrderedValue::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = OrderedMap::new();
+        while let Some(key) = map.next_key()? {
+            object.push((key, map.next_value_seed(self)?));
+        }
+        self.0.check(&object).map_err(A::Error::custom)?;
+        Ok(OrderedValue::Object(object))
+    }
+}
@@ -0,0 +1,87 @@
+//! Deserialize a [`RawNumberValue`] from `serde_json` by capturing each value as a [`RawValue`]
+//!
+//! A visitor is only handed numbers already converted, unless `serde_json` is built with
+//! `arbitrary_precision`, which would change every other user of the crate in the build too. A
+//! `RawValue` keeps its text instead: numbers are taken as is, and arrays and objects are parsed
+//! again from theirs, so each byte is read once per level of nesting.
+
+use std::fmt;
+
+use json_model::{RawNumberMap, RawNumberValue};
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, SeqAccess, Visitor};
+use serde_json::value::RawValue;
+
+/// A [`RawNumberValue`] with arrays and objects nested at most `depth` deep, standing in for
+/// `serde_json`'s recursion limit, which each nested parse starts over
+#[derive(Copy, Clone)]
+pub struct Raw {
+    pub depth: usize,
+}
+
+impl<'de> DeserializeSeed<'de> for Raw {
+    type Value = RawNumberValue<'de>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let text = <&RawValue as serde::Deserialize>::deserialize(deserializer)?.get();
+        if let Some(b'-' | b'0'..=b'9') = text.bytes().next() {
+            return Ok(RawNumberValue::Number(text));
+        }
+        serde_json::Deserializer::from_str(text)
+            .deserialize_any(ValueVisitor(self))
+            .map_err(D::Error::custom)
+    }
+}
+
+struct ValueVisitor(Raw);
+
+impl ValueVisitor {
+    /// The seed for the members of an array or object
+    fn nested<E: serde::de::Error>(&self) -> Result<Raw, E> {
+        match self.0.depth.checked_sub(1) {
+            Some(depth) => Ok(Raw { depth }),
+            None => Err(E::custom("recursion limit exceeded")),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = RawNumberValue<'de>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(RawNumberValue::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(RawNumberValue::Bool(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(RawNumberValue::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(RawNumberValue::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let seed = self.nested()?;
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element_seed(seed)? {
+            array.push(value);
+        }
+        Ok(RawNumberValue::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let seed = self.nested()?;
+        let mut object = RawNumberMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            object.insert(key, map.next_value_seed(seed)?);
+        }
+        Ok(RawNumberValue::Object(object))
+    }
+}
@@ -0,0 +1,121 @@
+//! [`parser`](crate::parser) borrowing strings from the input
+
+use std::borrow::Cow;
+
+use winnow::prelude::*;
+use winnow::Result;
+use winnow::{
+    ascii::float,
+    combinator::fail,
+    combinator::peek,
+    combinator::{delimited, preceded, separated_pair, terminated},
+    combinator::{dispatch, repeat, separated},
+    error::{AddContext, ModalError, ParserError, StrContext},
+    token::{any, take_while},
+};
+
+use json_model::{BorrowedMap, BorrowedValue};
+
+use crate::parser::{self, nest, ws, Stream};
+
+pub fn json<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    max_depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<BorrowedValue<'i>, E> {
+    delimited(ws, |i: &mut Stream<'i>| json_value(max_depth, i), ws).parse_next(input)
+}
+
+fn json_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<BorrowedValue<'i>, E> {
+    dispatch!(peek(any);
+        'n' => "null".value(BorrowedValue::Null),
+        't' => "true".value(BorrowedValue::Bool(true)),
+        'f' => "false".value(BorrowedValue::Bool(false)),
+        '"' => string.map(BorrowedValue::String),
+        '+' => float.map(BorrowedValue::Number),
+        '-' => float.map(BorrowedValue::Number),
+        '0'..='9' => float.map(BorrowedValue::Number),
+        '[' => (|i: &mut Stream<'i>| array(depth, i)).map(BorrowedValue::Array),
+        '{' => (|i: &mut Stream<'i>| object(depth, i)).map(BorrowedValue::Object),
+        _ => fail,
+    )
+    .parse_next(input)
+}
+
+fn string<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+    input: &mut Stream<'i>,
+) -> Result<Cow<'i, str>, E> {
+    preceded('\"', terminated(characters, '\"'))
+        .context(StrContext::Expected("string".into()))
+        .parse_next(input)
+}
+
+/// Borrow everything up to the first escape, only building a `String` if there is one
+fn characters<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<Cow<'i, str>, E> {
+    let unescaped = take_while(0.., |c| c != '\"' && c != '\\').parse_next(input)?;
+    if !input.starts_with('\\') {
+        return Ok(Cow::Borrowed(unescaped));
+    }
+    repeat(0.., parser::character)
+        .fold(
+            || unescaped.to_owned(),
+            |mut string, c| {
+                string.push(c);
+                string
+            },
+        )
+        .map(Cow::Owned)
+        .parse_next(input)
+}
+
+fn array<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<Vec<BorrowedValue<'i>>, E> {
+    let depth = nest(depth, input)?;
+    preceded(
+        ('[', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| json_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, ']'),
+        ),
+    )
+    .context(StrContext::Expected("array".into()))
+    .parse_next(input)
+}
+
+fn object<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<BorrowedMap<'i>, E> {
+    let depth = nest(depth, input)?;
+    preceded(
+        ('{', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| key_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, '}'),
+        ),
+    )
+    .context(StrContext::Expected("object".into()))
+    .parse_next(input)
+}
+
+fn key_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<(Cow<'i, str>, BorrowedValue<'i>), E> {
+    separated_pair(string, (ws, ':', ws), |i: &mut Stream<'i>| {
+        json_value(depth, i)
+    })
+    .parse_next(input)
+}
@@ -0,0 +1,112 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{JsonParser, ParseError, Value, MAX_DEPTH};
+use winnow::error::{ContextError, ErrMode, StrContext};
+use winnow::prelude::*;
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Winnow {
+    pub max_depth: usize,
+}
+
+impl Winnow {
+    pub const fn new(max_depth: usize) -> Self {
+        Winnow { max_depth }
+    }
+}
+
+impl Default for Winnow {
+    fn default() -> Self {
+        Winnow::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Winnow {
+    fn name(&self) -> &'static str {
+        "winnow"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        (|input: &mut &str| parser::json::<ErrMode<ContextError>>(self.max_depth, input))
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Winnow {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        (|input: &mut spanned::Stream<'_>| {
+            spanned::json::<ErrMode<ContextError>>(self.max_depth, input)
+        })
+        .parse(spanned::Stream::new(src))
+        .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Winnow {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        (|input: &mut &'a str| borrowed::json::<ErrMode<ContextError>>(self.max_depth, input))
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Winnow {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        (|input: &mut &'a str| raw_numbers::json::<ErrMode<ContextError>>(self.max_depth, input))
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Winnow {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        (|input: &mut &str| ordered::json::<ErrMode<ContextError>>(keys, self.max_depth, input))
+            .parse(src)
+            .map_err(|err| parse_error(&err))
+    }
+}
+
+/// The expected set is the `StrContext::Expected` contexts the parser attached
+pub fn parse_error<I>(err: &winnow::error::ParseError<I, ContextError>) -> ParseError
+where
+    winnow::error::ParseError<I, ContextError>: std::fmt::Display,
+{
+    let expected = err
+        .inner()
+        .context()
+        .filter_map(|context| match context {
+            StrContext::Expected(value) => Some(value.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    ParseError::new(err.to_string())
+        .with_offset(err.offset())
+        .with_expected(expected)
+}
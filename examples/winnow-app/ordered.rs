@@ -0,0 +1,114 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use winnow::prelude::*;
+use winnow::Result;
+use winnow::{
+    ascii::float,
+    combinator::fail,
+    combinator::peek,
+    combinator::{delimited, preceded, separated_pair, terminated},
+    combinator::{dispatch, separated},
+    error::{AddContext, ModalError, ParserError, StrContext, StrContextValue},
+    token::any,
+};
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+
+use crate::parser::{nest, string, ws, Stream};
+
+/// Repeated keys in an object are handled as `keys` says
+pub fn json<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    keys: DuplicateKeys,
+    max_depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<OrderedValue, E> {
+    delimited(ws, |i: &mut Stream<'i>| json_value(keys, max_depth, i), ws).parse_next(input)
+}
+
+fn json_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    keys: DuplicateKeys,
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<OrderedValue, E> {
+    dispatch!(peek(any);
+        'n' => "null".value(OrderedValue::Null),
+        't' => "true".value(OrderedValue::Bool(true)),
+        'f' => "false".value(OrderedValue::Bool(false)),
+        '"' => string.map(OrderedValue::String),
+        '+' => float.map(OrderedValue::Number),
+        '-' => float.map(OrderedValue::Number),
+        '0'..='9' => float.map(OrderedValue::Number),
+        '[' => (|i: &mut Stream<'i>| array(keys, depth, i)).map(OrderedValue::Array),
+        '{' => (|i: &mut Stream<'i>| object(keys, depth, i)).map(OrderedValue::Object),
+        _ => fail,
+    )
+    .parse_next(input)
+}
+
+fn array<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    keys: DuplicateKeys,
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<Vec<OrderedValue>, E> {
+    let depth = nest(depth, input)?;
+    preceded(
+        ('[', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| json_value(keys, depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, ']'),
+        ),
+    )
+    .context(StrContext::Expected("array".into()))
+    .parse_next(input)
+}
+
+fn object<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    keys: DuplicateKeys,
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<OrderedMap, E> {
+    let depth = nest(depth, input)?;
+    let start = input.checkpoint();
+    let members: OrderedMap = preceded(
+        ('{', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| key_value(keys, depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, '}'),
+        ),
+    )
+    .context(StrContext::Expected("object".into()))
+    .parse_next(input)?;
+    match keys.check(&members) {
+        Ok(()) => Ok(members),
+        // Only known once the whole object is parsed, so point back at its `{`
+        Err(_) => {
+            input.reset(&start);
+            Err(E::from_input(input)
+                .add_context(input, &start, StrContext::Label("object"))
+                .add_context(
+                    input,
+                    &start,
+                    StrContext::Expected(StrContextValue::Description("unique keys")),
+                ))
+        }
+    }
+}
+
+fn key_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    keys: DuplicateKeys,
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<(String, OrderedValue), E> {
+    separated_pair(string, (ws, ':', ws), |i: &mut Stream<'i>| {
+        json_value(keys, depth, i)
+    })
+    .parse_next(input)
+}
@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::str;
 
 use winnow::prelude::*;
@@ -11,11 +10,11 @@ use winnow::{
     combinator::{alt, dispatch},
     combinator::{delimited, preceded, separated_pair, terminated},
     combinator::{repeat, separated},
-    error::{AddContext, ParserError, StrContext},
+    error::{AddContext, ModalError, ParserError, StrContext},
     token::{any, none_of, take, take_while},
 };
 
-use crate::json::JsonValue;
+use json_model::{Map, Value};
 
 pub type Stream<'i> = &'i str;
 
@@ -30,29 +29,31 @@ pub type Stream<'i> = &'i str;
 /// Here we use `&str` as input type, but parsers can be generic over
 /// the input type, work directly with `&[u8]`, or any other type that
 /// implements the required traits.
-pub fn json<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+pub fn json<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    max_depth: usize,
     input: &mut Stream<'i>,
-) -> Result<JsonValue, E> {
-    delimited(ws, json_value, ws).parse_next(input)
+) -> Result<Value, E> {
+    delimited(ws, |i: &mut Stream<'i>| json_value(max_depth, i), ws).parse_next(input)
 }
 
 /// `alt` is a combinator that tries multiple parsers one by one, until
 /// one of them succeeds
-fn json_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+fn json_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
     input: &mut Stream<'i>,
-) -> Result<JsonValue, E> {
+) -> Result<Value, E> {
     // `dispatch` gives you `match`-like behavior compared to `alt` successively trying different
     // implementations.
     dispatch!(peek(any);
-        'n' => null.value(JsonValue::Null),
-        't' => true_.map(JsonValue::Boolean),
-        'f' => false_.map(JsonValue::Boolean),
-        '"' => string.map(JsonValue::Str),
-        '+' => float.map(JsonValue::Num),
-        '-' => float.map(JsonValue::Num),
-        '0'..='9' => float.map(JsonValue::Num),
-        '[' => array.map(JsonValue::Array),
-        '{' => object.map(JsonValue::Object),
+        'n' => null.value(Value::Null),
+        't' => true_.map(Value::Bool),
+        'f' => false_.map(Value::Bool),
+        '"' => string.map(Value::String),
+        '+' => float.map(Value::Number),
+        '-' => float.map(Value::Number),
+        '0'..='9' => float.map(Value::Number),
+        '[' => (|i: &mut Stream<'i>| array(depth, i)).map(Value::Array),
+        '{' => (|i: &mut Stream<'i>| object(depth, i)).map(Value::Object),
         _ => fail,
     )
     .parse_next(input)
@@ -85,7 +86,7 @@ fn false_<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<bool
 
 /// This parser gathers all `char`s up into a `String`with a parse to take the double quote
 /// character, before the string (using `preceded`) and after the string (using `terminated`).
-fn string<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+pub(crate) fn string<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
     input: &mut Stream<'i>,
 ) -> Result<String, E> {
     preceded(
@@ -106,7 +107,7 @@ fn string<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
 
 /// You can mix the above declarative parsing with an imperative style to handle more unique cases,
 /// like escaping
-fn character<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<char, E> {
+pub(crate) fn character<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<char, E> {
     let c = none_of('\"').parse_next(input)?;
     if c == '\\' {
         dispatch!(any;
@@ -159,38 +160,77 @@ fn u16_hex<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<u16
 /// accumulating results in a `Vec`, until it encounters an error.
 /// If you want more control on the parser application, check out the `iterator`
 /// combinator (cf `examples/iterator.rs`)
-fn array<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+fn array<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
     input: &mut Stream<'i>,
-) -> Result<Vec<JsonValue>, E> {
+) -> Result<Vec<Value>, E> {
+    let depth = nest(depth, input)?;
     preceded(
         ('[', ws),
-        terminated(separated(0.., json_value, (ws, ',', ws)), (ws, ']')),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| json_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, ']'),
+        ),
     )
     .context(StrContext::Expected("array".into()))
     .parse_next(input)
 }
 
-fn object<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+fn object<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
     input: &mut Stream<'i>,
-) -> Result<HashMap<String, JsonValue>, E> {
+) -> Result<Map, E> {
+    let depth = nest(depth, input)?;
     preceded(
         ('{', ws),
-        terminated(separated(0.., key_value, (ws, ',', ws)), (ws, '}')),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| key_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, '}'),
+        ),
     )
     .context(StrContext::Expected("object".into()))
     .parse_next(input)
 }
 
-fn key_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>>(
+fn key_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
     input: &mut Stream<'i>,
-) -> Result<(String, JsonValue), E> {
-    separated_pair(string, (ws, ':', ws), json_value).parse_next(input)
+) -> Result<(String, Value), E> {
+    separated_pair(string, (ws, ':', ws), |i: &mut Stream<'i>| {
+        json_value(depth, i)
+    })
+    .parse_next(input)
+}
+
+/// How many more levels may nest inside the array or object starting at `input`
+///
+/// Once `depth` is used up the error is cut, so that no caller backtracks over it, and deep input is
+/// rejected instead of overflowing the stack.
+pub(crate) fn nest<I, E>(depth: usize, input: &mut I) -> Result<usize, E>
+where
+    I: winnow::stream::Stream,
+    E: ParserError<I> + AddContext<I, StrContext> + ModalError,
+{
+    depth.checked_sub(1).ok_or_else(|| {
+        let start = input.checkpoint();
+        E::from_input(input)
+            .add_context(input, &start, StrContext::Label("nesting depth"))
+            .cut()
+    })
 }
 
 /// Parser combinators are constructed from the bottom up:
 /// first we write parsers for the smallest elements (here a space character),
 /// then we'll combine them in larger parsers
-fn ws<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<&'i str, E> {
+pub(crate) fn ws<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<&'i str, E> {
     // Combinators like `take_while` return a function. That function is the
     // parser,to which we can pass the input
     take_while(0.., WS).parse_next(input)
@@ -206,7 +246,7 @@ mod test {
 
     #[allow(clippy::useless_attribute)]
     #[allow(dead_code)] // its dead for benches
-    type Error = winnow::error::ContextError;
+    type Error = winnow::error::ErrMode<winnow::error::ContextError>;
 
     #[test]
     fn json_string() {
@@ -236,36 +276,42 @@ mod test {
 
     #[test]
     fn json_object() {
-        use JsonValue::{Num, Object, Str};
+        use Value::{Number, Object, String};
 
         let input = r#"{"a":42,"b":"x"}"#;
 
         let expected = Object(
             vec![
-                ("a".to_owned(), Num(42.0)),
-                ("b".to_owned(), Str("x".to_owned())),
+                ("a".to_owned(), Number(42.0)),
+                ("b".to_owned(), String("x".to_owned())),
             ]
             .into_iter()
             .collect(),
         );
 
-        assert_eq!(json::<Error>.parse_peek(input), Ok(("", expected)));
+        assert_eq!(
+            (|i: &mut Stream<'_>| json::<Error>(json_model::MAX_DEPTH, i)).parse_peek(input),
+            Ok(("", expected))
+        );
     }
 
     #[test]
     fn json_array() {
-        use JsonValue::{Array, Num, Str};
+        use Value::{Array, Number, String};
 
         let input = r#"[42,"x"]"#;
 
-        let expected = Array(vec![Num(42.0), Str("x".to_owned())]);
+        let expected = Array(vec![Number(42.0), String("x".to_owned())]);
 
-        assert_eq!(json::<Error>.parse_peek(input), Ok(("", expected)));
+        assert_eq!(
+            (|i: &mut Stream<'_>| json::<Error>(json_model::MAX_DEPTH, i)).parse_peek(input),
+            Ok(("", expected))
+        );
     }
 
     #[test]
     fn json_whitespace() {
-        use JsonValue::{Array, Boolean, Null, Num, Object, Str};
+        use Value::{Array, Bool, Null, Number, Object, String};
 
         let input = r#"
   {
@@ -282,33 +328,33 @@ mod test {
   "#;
 
         assert_eq!(
-            json::<Error>.parse_peek(input),
+            (|i: &mut Stream<'_>| json::<Error>(json_model::MAX_DEPTH, i)).parse_peek(input),
             Ok((
                 "",
                 Object(
                     vec![
                         ("null".to_owned(), Null),
-                        ("true".to_owned(), Boolean(true)),
-                        ("false".to_owned(), Boolean(false)),
-                        ("number".to_owned(), Num(123e4)),
-                        ("string".to_owned(), Str(" abc 123 ".to_owned())),
+                        ("true".to_owned(), Bool(true)),
+                        ("false".to_owned(), Bool(false)),
+                        ("number".to_owned(), Number(123e4)),
+                        ("string".to_owned(), String(" abc 123 ".to_owned())),
                         (
                             "array".to_owned(),
-                            Array(vec![Boolean(false), Num(1.0), Str("two".to_owned())])
+                            Array(vec![Bool(false), Number(1.0), String("two".to_owned())])
                         ),
                         (
                             "object".to_owned(),
                             Object(
                                 vec![
-                                    ("a".to_owned(), Num(1.0)),
-                                    ("b".to_owned(), Str("c".to_owned())),
+                                    ("a".to_owned(), Number(1.0)),
+                                    ("b".to_owned(), String("c".to_owned())),
                                 ]
                                 .into_iter()
                                 .collect()
                             )
                         ),
                         ("empty_array".to_owned(), Array(vec![]),),
-                        ("empty_object".to_owned(), Object(HashMap::new()),),
+                        ("empty_object".to_owned(), Object(Map::new()),),
                     ]
                     .into_iter()
                     .collect()
@@ -316,4 +362,18 @@ mod test {
             ))
         );
     }
+
+    #[test]
+    fn json_depth() {
+        use winnow::error::ErrMode;
+
+        let mut shallow = |i: &mut Stream<'_>| json::<Error>(2, i);
+        assert!(shallow.parse_peek(r#"{"a": []}"#).is_ok());
+        // Cut, so the error isn't backtracked over and replaced
+        assert!(matches!(shallow.parse_peek("[[[]]]"), Err(ErrMode::Cut(_))));
+        assert!(matches!(
+            shallow.parse_peek(r#"{"a": {"b": {}}}"#),
+            Err(ErrMode::Cut(_))
+        ));
+    }
 }
@@ -0,0 +1,98 @@
+//! [`parser`](crate::parser) keeping numbers as their text
+
+use winnow::prelude::*;
+use winnow::Result;
+use winnow::{
+    ascii::float,
+    combinator::fail,
+    combinator::peek,
+    combinator::{delimited, preceded, separated_pair, terminated},
+    combinator::{dispatch, separated},
+    error::{AddContext, ModalError, ParserError, StrContext},
+    token::any,
+};
+
+use json_model::{RawNumberMap, RawNumberValue};
+
+use crate::parser::{nest, string, ws, Stream};
+
+pub fn json<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    max_depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<RawNumberValue<'i>, E> {
+    delimited(ws, |i: &mut Stream<'i>| json_value(max_depth, i), ws).parse_next(input)
+}
+
+fn json_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<RawNumberValue<'i>, E> {
+    dispatch!(peek(any);
+        'n' => "null".value(RawNumberValue::Null),
+        't' => "true".value(RawNumberValue::Bool(true)),
+        'f' => "false".value(RawNumberValue::Bool(false)),
+        '"' => string.map(RawNumberValue::String),
+        '+' => number.map(RawNumberValue::Number),
+        '-' => number.map(RawNumberValue::Number),
+        '0'..='9' => number.map(RawNumberValue::Number),
+        '[' => (|i: &mut Stream<'i>| array(depth, i)).map(RawNumberValue::Array),
+        '{' => (|i: &mut Stream<'i>| object(depth, i)).map(RawNumberValue::Object),
+        _ => fail,
+    )
+    .parse_next(input)
+}
+
+/// The text [`float`] accepts, without converting it
+fn number<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<&'i str, E> {
+    float::<_, f64, _>.take().parse_next(input)
+}
+
+fn array<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<Vec<RawNumberValue<'i>>, E> {
+    let depth = nest(depth, input)?;
+    preceded(
+        ('[', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| json_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, ']'),
+        ),
+    )
+    .context(StrContext::Expected("array".into()))
+    .parse_next(input)
+}
+
+fn object<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<RawNumberMap<'i>, E> {
+    let depth = nest(depth, input)?;
+    preceded(
+        ('{', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| key_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, '}'),
+        ),
+    )
+    .context(StrContext::Expected("object".into()))
+    .parse_next(input)
+}
+
+fn key_value<'i, E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError>(
+    depth: usize,
+    input: &mut Stream<'i>,
+) -> Result<(String, RawNumberValue<'i>), E> {
+    separated_pair(string, (ws, ':', ws), |i: &mut Stream<'i>| {
+        json_value(depth, i)
+    })
+    .parse_next(input)
+}
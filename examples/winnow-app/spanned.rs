@@ -0,0 +1,124 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value, from
+//! [`Parser::with_span`]
+
+use winnow::prelude::*;
+use winnow::Result;
+use winnow::{
+    ascii::float,
+    combinator::fail,
+    combinator::peek,
+    combinator::{delimited, preceded, separated_pair, terminated},
+    combinator::{dispatch, separated},
+    error::{AddContext, ModalError, ParserError, StrContext},
+    stream::LocatingSlice,
+    token::{any, take_while},
+};
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+
+use crate::parser::{self, nest};
+
+/// A [`parser::Stream`] that tracks its offset
+pub type Stream<'i> = LocatingSlice<&'i str>;
+
+pub fn json<'i, E>(max_depth: usize, input: &mut Stream<'i>) -> Result<Spanned<SpannedValue>, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError,
+    E: ParserError<parser::Stream<'i>> + AddContext<parser::Stream<'i>, StrContext>,
+{
+    delimited(ws, |i: &mut Stream<'i>| json_value(max_depth, i), ws).parse_next(input)
+}
+
+fn json_value<'i, E>(depth: usize, input: &mut Stream<'i>) -> Result<Spanned<SpannedValue>, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError,
+    E: ParserError<parser::Stream<'i>> + AddContext<parser::Stream<'i>, StrContext>,
+{
+    dispatch!(peek(any);
+        'n' => "null".value(SpannedValue::Null),
+        't' => "true".value(SpannedValue::Bool(true)),
+        'f' => "false".value(SpannedValue::Bool(false)),
+        '"' => unlocated(parser::string).map(SpannedValue::String),
+        '+' => float.map(SpannedValue::Number),
+        '-' => float.map(SpannedValue::Number),
+        '0'..='9' => float.map(SpannedValue::Number),
+        '[' => (|i: &mut Stream<'i>| array(depth, i)).map(SpannedValue::Array),
+        '{' => (|i: &mut Stream<'i>| object(depth, i)).map(SpannedValue::Object),
+        _ => fail,
+    )
+    .with_span()
+    .map(|(value, span)| Spanned::new(value, span))
+    .parse_next(input)
+}
+
+fn array<'i, E>(depth: usize, input: &mut Stream<'i>) -> Result<Vec<Spanned<SpannedValue>>, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError,
+    E: ParserError<parser::Stream<'i>> + AddContext<parser::Stream<'i>, StrContext>,
+{
+    let depth = nest(depth, input)?;
+    preceded(
+        ('[', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| json_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, ']'),
+        ),
+    )
+    .context(StrContext::Expected("array".into()))
+    .parse_next(input)
+}
+
+fn object<'i, E>(depth: usize, input: &mut Stream<'i>) -> Result<Vec<SpannedMember>, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError,
+    E: ParserError<parser::Stream<'i>> + AddContext<parser::Stream<'i>, StrContext>,
+{
+    let depth = nest(depth, input)?;
+    preceded(
+        ('{', ws),
+        terminated(
+            separated(
+                0..,
+                move |i: &mut Stream<'i>| key_value(depth, i),
+                (ws, ',', ws),
+            ),
+            (ws, '}'),
+        ),
+    )
+    .context(StrContext::Expected("object".into()))
+    .parse_next(input)
+}
+
+fn key_value<'i, E>(depth: usize, input: &mut Stream<'i>) -> Result<SpannedMember, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext> + ModalError,
+    E: ParserError<parser::Stream<'i>> + AddContext<parser::Stream<'i>, StrContext>,
+{
+    let key = unlocated(parser::string)
+        .with_span()
+        .map(|(key, span)| Spanned::new(key, span));
+    separated_pair(key, (ws, ':', ws), |i: &mut Stream<'i>| {
+        json_value(depth, i)
+    })
+    .parse_next(input)
+}
+
+fn ws<'i, E: ParserError<Stream<'i>>>(input: &mut Stream<'i>) -> Result<&'i str, E> {
+    take_while(0.., [' ', '\t', '\r', '\n']).parse_next(input)
+}
+
+/// Run one of [`parser`]'s `&str` parsers, which don't need to know where they are
+fn unlocated<'i, O, E>(
+    mut inner: impl Parser<parser::Stream<'i>, O, E>,
+) -> impl Parser<Stream<'i>, O, E> {
+    move |input: &mut Stream<'i>| {
+        let mut rest: &'i str = input;
+        let output = inner.parse_next(&mut rest)?;
+        input.next_slice(input.len() - rest.len());
+        Ok(output)
+    }
+}
@@ -0,0 +1,128 @@
+//! [`parser`](crate::parser) borrowing strings from the input, which must be a [`StrTokens`]
+
+use std::borrow::Cow;
+
+use json_model::{BorrowedMap, BorrowedValue};
+use yap::types::StrTokens;
+use yap::{IntoTokens, Tokens};
+
+use crate::parser::{self, bool, field_separator, null, number, skip_whitespace, Error, ErrorKind};
+
+/// Parse JSON from a string: a `value()` with nothing but whitespace around it.
+pub fn parse(s: &str, max_depth: usize) -> Result<BorrowedValue<'_>, Error> {
+    parser::document(&mut s.into_tokens(), |toks| value(toks, max_depth))
+}
+
+fn value<'a>(toks: &mut StrTokens<'a>, depth: usize) -> Result<BorrowedValue<'a>, Error> {
+    let value = yap::one_of!(ts from toks;
+        array(ts, depth).map(|res| res.map(BorrowedValue::Array)),
+        string(ts).map(|res| res.map(BorrowedValue::String)),
+        object(ts, depth).map(|res| res.map(BorrowedValue::Object)),
+        number(ts).map(|v| Ok(BorrowedValue::Number(v))),
+        bool(ts).map(|v| Ok(BorrowedValue::Bool(v))),
+        null(ts).then_some(Ok(BorrowedValue::Null))
+    );
+
+    match value {
+        Some(r) => r,
+        None => Err(ErrorKind::InvalidJson.at(toks.location(), toks.location())),
+    }
+}
+
+fn array<'a>(
+    toks: &mut StrTokens<'a>,
+    depth: usize,
+) -> Option<Result<Vec<BorrowedValue<'a>>, Error>> {
+    let start = toks.location();
+    if !toks.token('[') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values = toks
+        .sep_by(|t| value(t, depth).ok(), field_separator)
+        .collect();
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(']') {
+        return Some(Err(ErrorKind::ArrayNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object<'a>(toks: &mut StrTokens<'a>, depth: usize) -> Option<Result<BorrowedMap<'a>, Error>> {
+    let start = toks.location();
+    if !toks.token('{') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values: Result<BorrowedMap<'a>, Error> = toks
+        .sep_by(|t| object_field(t, depth), field_separator)
+        .collect();
+    let Ok(values) = values else {
+        return Some(values);
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token('}') {
+        return Some(Err(ErrorKind::ObjectNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object_field<'a>(
+    toks: &mut StrTokens<'a>,
+    depth: usize,
+) -> Option<Result<(Cow<'a, str>, BorrowedValue<'a>), Error>> {
+    if toks.peek() == Some('}') {
+        return None;
+    }
+    let start = toks.location();
+
+    let name = match string(&mut *toks) {
+        None => return Some(Err(ErrorKind::InvalidObjectField.at(start, start))),
+        Some(Err(err)) => return Some(Err(err)),
+        Some(Ok(s)) => s,
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(':') {
+        let loc = toks.location();
+        return Some(Err(ErrorKind::MissingObjectFieldSeparator.at(loc, loc)));
+    }
+    skip_whitespace(&mut *toks);
+
+    let val = match value(&mut *toks, depth) {
+        Ok(val) => val,
+        Err(e) => return Some(Err(e)),
+    };
+
+    Some(Ok((name, val)))
+}
+
+/// Borrow the string if it has no escapes, otherwise start over with [`parser::string`]
+fn string<'a>(toks: &mut StrTokens<'a>) -> Option<Result<Cow<'a, str>, Error>> {
+    let start = toks.location();
+    if !toks.token('"') {
+        return None;
+    }
+
+    let contents = toks.remaining();
+    toks.skip_while(|&c| c != '"' && c != '\\');
+    let len = contents.len() - toks.remaining().len();
+    if toks.token('"') {
+        return Some(Ok(Cow::Borrowed(&contents[..len])));
+    }
+
+    toks.set_location(start);
+    parser::string(toks).map(|res| res.map(Cow::Owned))
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+#[cfg(feature = "ordered")]
+pub mod ordered;
+pub mod parser;
+#[cfg(feature = "raw-numbers")]
+pub mod raw_numbers;
+#[cfg(feature = "spanned")]
+pub mod spanned;
+
+use json_model::{JsonParser, ParseError, Value, MAX_DEPTH};
+
+/// Arrays and objects nested more than `max_depth` deep are rejected
+#[derive(Copy, Clone, Debug)]
+pub struct Yap {
+    pub max_depth: usize,
+}
+
+impl Yap {
+    pub const fn new(max_depth: usize) -> Self {
+        Yap { max_depth }
+    }
+}
+
+impl Default for Yap {
+    fn default() -> Self {
+        Yap::new(MAX_DEPTH)
+    }
+}
+
+impl JsonParser for Yap {
+    fn name(&self) -> &'static str {
+        "yap"
+    }
+
+    fn parse(&self, src: &str) -> Result<Value, ParseError> {
+        parser::parse(src, self.max_depth).map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "spanned")]
+impl json_model::SpannedParser for Yap {
+    fn parse_spanned(
+        &self,
+        src: &str,
+    ) -> Result<json_model::Spanned<json_model::SpannedValue>, ParseError> {
+        spanned::parse(src, self.max_depth).map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "borrowed")]
+impl json_model::BorrowedParser for Yap {
+    fn parse_borrowed<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::BorrowedValue<'a>, ParseError> {
+        borrowed::parse(src, self.max_depth).map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "raw-numbers")]
+impl json_model::RawNumberParser for Yap {
+    fn parse_raw_numbers<'a>(
+        &self,
+        src: &'a str,
+    ) -> Result<json_model::RawNumberValue<'a>, ParseError> {
+        raw_numbers::parse(src, self.max_depth).map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "ordered")]
+impl json_model::OrderedParser for Yap {
+    fn parse_ordered(
+        &self,
+        src: &str,
+        keys: json_model::DuplicateKeys,
+    ) -> Result<json_model::OrderedValue, ParseError> {
+        ordered::parse(src, self.max_depth, keys).map_err(ParseError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> String {
+        format!("{}{}", "[".repeat(depth), "]".repeat(depth))
+    }
+
+    #[test]
+    fn limits_depth() {
+        assert!(Yap::default().parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(Yap::default().parse(&nested(MAX_DEPTH + 1)).is_err());
+        assert!(Yap::default().parse(&nested(100_000)).is_err());
+
+        // Errors inside arrays are reported as the array not being closed, so nest objects here
+        let src = r#"{"a": {"b": {}}}"#;
+        assert!(parser::parse(src, 3).is_ok());
+        let err = ParseError::from(parser::parse(src, 2).unwrap_err());
+        assert_eq!(err.message, "TooDeep");
+        assert_eq!(err.offset, Some(12));
+    }
+}
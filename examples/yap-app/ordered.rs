@@ -0,0 +1,128 @@
+//! [`parser`](crate::parser) keeping object members in order
+
+use json_model::{DuplicateKeys, OrderedMap, OrderedValue};
+use yap::{IntoTokens, Tokens};
+
+use crate::parser::{
+    bool, document, field_separator, null, number, skip_whitespace, string, Error, ErrorKind,
+};
+
+/// Parse JSON from a string: a `value()` with nothing but whitespace around it.
+///
+/// Repeated keys in an object are handled as `keys` says.
+pub fn parse(s: &str, max_depth: usize, keys: DuplicateKeys) -> Result<OrderedValue, Error> {
+    document(&mut s.into_tokens(), |toks| value(toks, max_depth, keys))
+}
+
+fn value(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Result<OrderedValue, Error> {
+    let value = yap::one_of!(ts from toks;
+        array(ts, depth, keys).map(|res| res.map(OrderedValue::Array)),
+        string(ts).map(|res| res.map(OrderedValue::String)),
+        object(ts, depth, keys).map(|res| res.map(OrderedValue::Object)),
+        number(ts).map(|v| Ok(OrderedValue::Number(v))),
+        bool(ts).map(|v| Ok(OrderedValue::Bool(v))),
+        null(ts).then_some(Ok(OrderedValue::Null))
+    );
+
+    match value {
+        Some(r) => r,
+        None => Err(ErrorKind::InvalidJson.at(toks.location(), toks.location())),
+    }
+}
+
+fn array(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Option<Result<Vec<OrderedValue>, Error>> {
+    let start = toks.location();
+    if !toks.token('[') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values = toks
+        .sep_by(|t| value(t, depth, keys).ok(), |t| field_separator(t))
+        .collect();
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(']') {
+        return Some(Err(ErrorKind::ArrayNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Option<Result<OrderedMap, Error>> {
+    let start = toks.location();
+    if !toks.token('{') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values: Result<OrderedMap, Error> = toks
+        .sep_by(|t| object_field(t, depth, keys), |t| field_separator(t))
+        .collect();
+    let Ok(values) = values else {
+        return Some(values);
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token('}') {
+        return Some(Err(ErrorKind::ObjectNotClosed.at(start, toks.location())));
+    }
+    if let Err(err) = keys.check(&values) {
+        return Some(Err(
+            ErrorKind::DuplicateKey(err.0).at(start, toks.location())
+        ));
+    }
+
+    Some(Ok(values))
+}
+
+fn object_field(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+    keys: DuplicateKeys,
+) -> Option<Result<(String, OrderedValue), Error>> {
+    if toks.peek() == Some('}') {
+        return None;
+    }
+    let start = toks.location();
+
+    let name = match string(&mut *toks) {
+        None => return Some(Err(ErrorKind::InvalidObjectField.at(start.clone(), start))),
+        Some(Err(err)) => return Some(Err(err)),
+        Some(Ok(s)) => s,
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(':') {
+        let loc = toks.location();
+        return Some(Err(
+            ErrorKind::MissingObjectFieldSeparator.at(loc.clone(), loc)
+        ));
+    }
+    skip_whitespace(&mut *toks);
+
+    let val = match value(&mut *toks, depth, keys) {
+        Ok(val) => val,
+        Err(e) => return Some(Err(e)),
+    };
+
+    Some(Ok((name, val)))
+}
@@ -1,20 +1,12 @@
-use std::collections::HashMap;
+use json_model::{Map, ParseError, Value};
 use yap::{IntoTokens, TokenLocation, Tokens};
 
-/// Parse JSON from a string. Just a very thin wrapper around `value()`.
-pub fn parse(s: &str) -> Result<Value, Error> {
-    value(&mut s.into_tokens())
-}
-
-/// This is what we'll parse our JSON into.
-#[derive(Clone, PartialEq, Debug)]
-pub enum Value {
-    Null,
-    Number(f64),
-    String(String),
-    Bool(bool),
-    Array(Vec<Value>),
-    Object(HashMap<String, Value>),
+/// Parse JSON from a string: a `value()` with nothing but whitespace around it.
+///
+/// At most `max_depth` arrays and objects may be nested, so that deep input is an error rather
+/// than a stack overflow.
+pub fn parse(s: &str, max_depth: usize) -> Result<Value, Error> {
+    document(&mut s.into_tokens(), |toks| value(toks, max_depth))
 }
 
 /// Some errors that can be emitted if things go wrong.
@@ -28,8 +20,16 @@ pub struct Error {
     kind: ErrorKind,
 }
 
+impl From<Error> for ParseError {
+    fn from(err: Error) -> Self {
+        ParseError::new(format!("{:?}", err.kind))
+            .with_offset(err.location.0)
+            .with_expected(err.kind.expected().iter().copied())
+    }
+}
+
 #[derive(PartialEq, Debug)]
-enum ErrorKind {
+pub(crate) enum ErrorKind {
     // No ']' seen while parsing array.
     ArrayNotClosed,
     // No '}' seen while parsing object.
@@ -40,14 +40,42 @@ enum ErrorKind {
     MissingObjectFieldSeparator,
     // String escape char (ie char after \) isn't valid.
     InvalidEscapeChar(char),
+    // \u isn't followed by four hex digits.
+    InvalidUnicodeEscape,
+    // \u escapes half of a surrogate pair without the other half.
+    LoneSurrogate(u32),
+    // Arrays and objects are nested deeper than allowed.
+    TooDeep,
+    // An object has the same key twice, which the parse was asked to reject.
+    DuplicateKey(String),
     // the file ended while we were still parsing.
     UnexpectedEof,
+    // Something other than whitespace follows the value.
+    TrailingInput,
     // We didn't successfully parse any valid JSON at all.
     InvalidJson,
 }
 
 impl ErrorKind {
-    fn at<T: TokenLocation>(self, start: T, end: T) -> Error {
+    /// What would have avoided the error
+    fn expected(&self) -> &'static [&'static str] {
+        match self {
+            ErrorKind::ArrayNotClosed => &["','", "']'"],
+            ErrorKind::ObjectNotClosed => &["','", "'}'"],
+            ErrorKind::InvalidObjectField => &["string"],
+            ErrorKind::MissingObjectFieldSeparator => &["':'"],
+            ErrorKind::InvalidEscapeChar(_) => &["escape sequence"],
+            ErrorKind::InvalidUnicodeEscape => &["hex digit"],
+            ErrorKind::LoneSurrogate(_) => &["escape sequence"],
+            ErrorKind::TooDeep => &[],
+            ErrorKind::DuplicateKey(_) => &[],
+            ErrorKind::UnexpectedEof => &[],
+            ErrorKind::TrailingInput => &["end of input"],
+            ErrorKind::InvalidJson => &["value"],
+        }
+    }
+
+    pub(crate) fn at<T: TokenLocation>(self, start: T, end: T) -> Error {
         Error {
             location: (start.offset(), end.offset()),
             kind: self,
@@ -59,13 +87,13 @@ impl ErrorKind {
 ///
 /// Try parsing each of the different types of value we know about,
 /// and return the first error that we encounter, or a valid `Value`.
-fn value(toks: &mut impl Tokens<Item = char>) -> Result<Value, Error> {
+fn value(toks: &mut impl Tokens<Item = char>, depth: usize) -> Result<Value, Error> {
     // Return the first thing we parse successfully from our token stream,
     // mapping values into their `Value` container.
     let value = yap::one_of!(ts from toks;
-        array(ts).map(|res| res.map(Value::Array)),
+        array(ts, depth).map(|res| res.map(Value::Array)),
         string(ts).map(|res| res.map(Value::String)),
-        object(ts).map(|res| res.map(Value::Object)),
+        object(ts, depth).map(|res| res.map(Value::Object)),
         number(ts).map(|v| Ok(Value::Number(v))),
         bool(ts).map(|v| Ok(Value::Bool(v))),
         null(ts).then_some(Ok(Value::Null))
@@ -85,7 +113,7 @@ fn value(toks: &mut impl Tokens<Item = char>) -> Result<Value, Error> {
 /// - `Some(Ok(values))` means we successfully parsed 0 or more array values.
 /// - `Some(Err(e))` means that we hit an error parsing the array.
 /// - `None` means that this wasn't an array and so nothing was parsed.
-fn array(toks: &mut impl Tokens<Item = char>) -> Option<Result<Vec<Value>, Error>> {
+fn array(toks: &mut impl Tokens<Item = char>, depth: usize) -> Option<Result<Vec<Value>, Error>> {
     // Note the location of the start of the array.
     let start = toks.location();
 
@@ -93,11 +121,14 @@ fn array(toks: &mut impl Tokens<Item = char>) -> Option<Result<Vec<Value>, Error
     if !toks.token('[') {
         return None;
     }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
     skip_whitespace(&mut *toks);
 
     // Use our `value()` parser to parse each array value, separated by ','.
     let values: Vec<Value> = toks
-        .sep_by(|t| value(t).ok(), |t| field_separator(t))
+        .sep_by(|t| value(t, depth).ok(), |t| field_separator(t))
         .collect();
 
     skip_whitespace(&mut *toks);
@@ -115,7 +146,7 @@ fn array(toks: &mut impl Tokens<Item = char>) -> Option<Result<Vec<Value>, Error
 /// - `Some(Ok(values))` means we successfully parsed 0 or more object values.
 /// - `Some(Err(e))` means that we hit an error parsing the object.
 /// - `None` means that this wasn't an object and so nothing was parsed.
-fn object(toks: &mut impl Tokens<Item = char>) -> Option<Result<HashMap<String, Value>, Error>> {
+fn object(toks: &mut impl Tokens<Item = char>, depth: usize) -> Option<Result<Map, Error>> {
     // Note the location of the start of the object.
     let start = toks.location();
 
@@ -123,11 +154,14 @@ fn object(toks: &mut impl Tokens<Item = char>) -> Option<Result<HashMap<String,
     if !toks.token('{') {
         return None;
     }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
     skip_whitespace(&mut *toks);
 
     // Expect object fields like `name: value` to be separated like arrays are.
-    let values: Result<HashMap<String, Value>, Error> = toks
-        .sep_by(|t| object_field(t), |t| field_separator(t))
+    let values: Result<Map, Error> = toks
+        .sep_by(|t| object_field(t, depth), |t| field_separator(t))
         .collect();
 
     // If we hit any errors above, return it.
@@ -149,7 +183,10 @@ fn object(toks: &mut impl Tokens<Item = char>) -> Option<Result<HashMap<String,
 /// - `Some(Ok((key, val)))` means we parsed a keyval field pair.
 /// - `Some(Err(e))` means we hit some unrecoverable error.
 /// - `None` means we parsed nothing and hit the end of the object.
-fn object_field(toks: &mut impl Tokens<Item = char>) -> Option<Result<(String, Value), Error>> {
+fn object_field(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+) -> Option<Result<(String, Value), Error>> {
     if toks.peek() == Some('}') {
         return None;
     }
@@ -173,7 +210,7 @@ fn object_field(toks: &mut impl Tokens<Item = char>) -> Option<Result<(String, V
     skip_whitespace(&mut *toks);
 
     // And after the name comes some arbitrary value:
-    let val = match value(&mut *toks) {
+    let val = match value(&mut *toks, depth) {
         Ok(val) => val,
         Err(e) => return Some(Err(e)),
     };
@@ -187,7 +224,7 @@ fn object_field(toks: &mut impl Tokens<Item = char>) -> Option<Result<(String, V
 /// - `None` if nothing consumed and not a string
 /// - `Some(Ok(s))` if we parsed a string successfully
 /// - `Some(Err(e))` if something went wrong parsing a string.
-fn string(toks: &mut impl Tokens<Item = char>) -> Option<Result<String, Error>> {
+pub(crate) fn string(toks: &mut impl Tokens<Item = char>) -> Option<Result<String, Error>> {
     // Try to consume a '"'. If we can't, we consume nothing and bail.
     if !toks.token('"') {
         return None;
@@ -198,7 +235,7 @@ fn string(toks: &mut impl Tokens<Item = char>) -> Option<Result<String, Error>>
     let mut s = String::new();
     while let Some(char) = toks.next() {
         match char {
-            // Handle escape chars (naively; ignore \uXXX for instance):
+            // Handle escape chars:
             '\\' => {
                 let Some(escape_char) = toks.next() else {
                     let loc = toks.location();
@@ -208,8 +245,18 @@ fn string(toks: &mut impl Tokens<Item = char>) -> Option<Result<String, Error>>
                     'n' => '\n',
                     't' => '\t',
                     'r' => '\r',
+                    'b' => '\x08',
+                    'f' => '\x0C',
                     '"' => '"',
                     '\\' => '\\',
+                    '/' => '/',
+                    'u' => match unicode_escape(toks) {
+                        Ok(c) => c,
+                        Err(kind) => {
+                            let loc = toks.location();
+                            return Some(Err(kind.at(loc.clone(), loc)));
+                        }
+                    },
                     // If we don't recognise the escape char, return an error:
                     c => {
                         let loc = toks.location();
@@ -231,8 +278,35 @@ fn string(toks: &mut impl Tokens<Item = char>) -> Option<Result<String, Error>>
     Some(Err(ErrorKind::UnexpectedEof.at(loc.clone(), loc)))
 }
 
+/// The char escaped by `\uXXXX`, once the `\u` has been consumed. A high surrogate has to be
+/// followed by a `\uXXXX` low surrogate, and the two are combined; a lone surrogate is an error,
+/// as it can't be stored in a `String`.
+fn unicode_escape(toks: &mut impl Tokens<Item = char>) -> Result<char, ErrorKind> {
+    let high = hex4(toks).ok_or(ErrorKind::InvalidUnicodeEscape)?;
+    if !(0xD800..0xDC00).contains(&high) {
+        return char::from_u32(high).ok_or(ErrorKind::LoneSurrogate(high));
+    }
+    if !toks.tokens("\\u".chars()) {
+        return Err(ErrorKind::LoneSurrogate(high));
+    }
+    let low = hex4(toks).ok_or(ErrorKind::InvalidUnicodeEscape)?;
+    if !(0xDC00..0xE000).contains(&low) {
+        return Err(ErrorKind::LoneSurrogate(high));
+    }
+    Ok(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).unwrap())
+}
+
+/// Four hex digits; None if there aren't!
+fn hex4(toks: &mut impl Tokens<Item = char>) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..4 {
+        value = value * 16 + toks.next()?.to_digit(16)?;
+    }
+    Some(value)
+}
+
 /// true or false; None if neither!
-fn bool(toks: &mut impl Tokens<Item = char>) -> Option<bool> {
+pub(crate) fn bool(toks: &mut impl Tokens<Item = char>) -> Option<bool> {
     yap::one_of!(toks;
         toks.tokens("true".chars()).then_some(true),
         toks.tokens("false".chars()).then_some(false)
@@ -240,21 +314,36 @@ fn bool(toks: &mut impl Tokens<Item = char>) -> Option<bool> {
 }
 
 // Is null seen? None if not.
-fn null(toks: &mut impl Tokens<Item = char>) -> bool {
+pub(crate) fn null(toks: &mut impl Tokens<Item = char>) -> bool {
     toks.tokens("null".chars())
 }
 
 /// Use the [`yap::chars::parse_f64`] helper function to parse
 /// anything that rust considers a valid float (which is a little more
 /// permissive than the JSON standard, actually).
-fn number(toks: &mut impl Tokens<Item = char>) -> Option<f64> {
+pub(crate) fn number(toks: &mut impl Tokens<Item = char>) -> Option<f64> {
     yap::chars::parse_f64::<String>(toks)
 }
 
-fn skip_whitespace(toks: &mut impl Tokens<Item = char>) {
+/// The whole input as one `value`, with only whitespace around it
+pub(crate) fn document<T, Toks: Tokens<Item = char>>(
+    toks: &mut Toks,
+    value: impl FnOnce(&mut Toks) -> Result<T, Error>,
+) -> Result<T, Error> {
+    skip_whitespace(&mut *toks);
+    let value = value(&mut *toks)?;
+    skip_whitespace(&mut *toks);
+    let start = toks.location();
+    match toks.next() {
+        None => Ok(value),
+        Some(_) => Err(ErrorKind::TrailingInput.at(start, toks.location())),
+    }
+}
+
+pub(crate) fn skip_whitespace(toks: &mut impl Tokens<Item = char>) {
     toks.skip_while(|c| c.is_ascii_whitespace());
 }
 
-fn field_separator(toks: &mut impl Tokens<Item = char>) -> bool {
+pub(crate) fn field_separator(toks: &mut impl Tokens<Item = char>) -> bool {
     toks.surrounded_by(|t| t.token(','), |t| skip_whitespace(t))
 }
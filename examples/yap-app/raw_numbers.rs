@@ -0,0 +1,117 @@
+//! [`parser`](crate::parser) keeping each number's text from the input, which must be a
+//! [`StrTokens`]
+
+use json_model::{RawNumberMap, RawNumberValue};
+use yap::types::StrTokens;
+use yap::{IntoTokens, Tokens};
+
+use crate::parser::{self, bool, field_separator, null, skip_whitespace, Error, ErrorKind};
+
+/// Parse JSON from a string: a `value()` with nothing but whitespace around it.
+pub fn parse(s: &str, max_depth: usize) -> Result<RawNumberValue<'_>, Error> {
+    parser::document(&mut s.into_tokens(), |toks| value(toks, max_depth))
+}
+
+fn value<'a>(toks: &mut StrTokens<'a>, depth: usize) -> Result<RawNumberValue<'a>, Error> {
+    let value = yap::one_of!(ts from toks;
+        array(ts, depth).map(|res| res.map(RawNumberValue::Array)),
+        parser::string(ts).map(|res| res.map(RawNumberValue::String)),
+        object(ts, depth).map(|res| res.map(RawNumberValue::Object)),
+        number(ts).map(|v| Ok(RawNumberValue::Number(v))),
+        bool(ts).map(|v| Ok(RawNumberValue::Bool(v))),
+        null(ts).then_some(Ok(RawNumberValue::Null))
+    );
+
+    match value {
+        Some(r) => r,
+        None => Err(ErrorKind::InvalidJson.at(toks.location(), toks.location())),
+    }
+}
+
+fn array<'a>(
+    toks: &mut StrTokens<'a>,
+    depth: usize,
+) -> Option<Result<Vec<RawNumberValue<'a>>, Error>> {
+    let start = toks.location();
+    if !toks.token('[') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values = toks
+        .sep_by(|t| value(t, depth).ok(), field_separator)
+        .collect();
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(']') {
+        return Some(Err(ErrorKind::ArrayNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object<'a>(toks: &mut StrTokens<'a>, depth: usize) -> Option<Result<RawNumberMap<'a>, Error>> {
+    let start = toks.location();
+    if !toks.token('{') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values: Result<RawNumberMap<'a>, Error> = toks
+        .sep_by(|t| object_field(t, depth), field_separator)
+        .collect();
+    let Ok(values) = values else {
+        return Some(values);
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token('}') {
+        return Some(Err(ErrorKind::ObjectNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object_field<'a>(
+    toks: &mut StrTokens<'a>,
+    depth: usize,
+) -> Option<Result<(String, RawNumberValue<'a>), Error>> {
+    if toks.peek() == Some('}') {
+        return None;
+    }
+    let start = toks.location();
+
+    let name = match parser::string(&mut *toks) {
+        None => return Some(Err(ErrorKind::InvalidObjectField.at(start, start))),
+        Some(Err(err)) => return Some(Err(err)),
+        Some(Ok(s)) => s,
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(':') {
+        let loc = toks.location();
+        return Some(Err(ErrorKind::MissingObjectFieldSeparator.at(loc, loc)));
+    }
+    skip_whitespace(&mut *toks);
+
+    let val = match value(&mut *toks, depth) {
+        Ok(val) => val,
+        Err(e) => return Some(Err(e)),
+    };
+
+    Some(Ok((name, val)))
+}
+
+/// The text [`parser::number`] accepts, without converting it
+fn number<'a>(toks: &mut StrTokens<'a>) -> Option<&'a str> {
+    let contents = toks.remaining();
+    parser::number(&mut *toks)?;
+    let len = contents.len() - toks.remaining().len();
+    Some(&contents[..len])
+}
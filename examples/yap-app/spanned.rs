@@ -0,0 +1,119 @@
+//! [`parser`](crate::parser) with a [`Span`](json_model::Span) on every value, from
+//! [`Tokens::location`]
+
+use json_model::{Spanned, SpannedMember, SpannedValue};
+use yap::{IntoTokens, TokenLocation, Tokens};
+
+use crate::parser::{
+    bool, document, field_separator, null, number, skip_whitespace, string, Error, ErrorKind,
+};
+
+/// Parse JSON from a string: a `value()` with nothing but whitespace around it.
+pub fn parse(s: &str, max_depth: usize) -> Result<Spanned<SpannedValue>, Error> {
+    document(&mut s.into_tokens(), |toks| value(toks, max_depth))
+}
+
+fn value(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+) -> Result<Spanned<SpannedValue>, Error> {
+    let start = toks.location();
+    let value = yap::one_of!(ts from toks;
+        array(ts, depth).map(|res| res.map(SpannedValue::Array)),
+        string(ts).map(|res| res.map(SpannedValue::String)),
+        object(ts, depth).map(|res| res.map(SpannedValue::Object)),
+        number(ts).map(|v| Ok(SpannedValue::Number(v))),
+        bool(ts).map(|v| Ok(SpannedValue::Bool(v))),
+        null(ts).then_some(Ok(SpannedValue::Null))
+    );
+
+    match value {
+        Some(r) => r.map(|value| Spanned::new(value, start.offset()..toks.offset())),
+        None => Err(ErrorKind::InvalidJson.at(start.clone(), start)),
+    }
+}
+
+fn array(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+) -> Option<Result<Vec<Spanned<SpannedValue>>, Error>> {
+    let start = toks.location();
+    if !toks.token('[') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values = toks
+        .sep_by(|t| value(t, depth).ok(), |t| field_separator(t))
+        .collect();
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(']') {
+        return Some(Err(ErrorKind::ArrayNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+) -> Option<Result<Vec<SpannedMember>, Error>> {
+    let start = toks.location();
+    if !toks.token('{') {
+        return None;
+    }
+    let Some(depth) = depth.checked_sub(1) else {
+        return Some(Err(ErrorKind::TooDeep.at(start, toks.location())));
+    };
+    skip_whitespace(&mut *toks);
+
+    let values: Result<Vec<SpannedMember>, Error> = toks
+        .sep_by(|t| object_field(t, depth), |t| field_separator(t))
+        .collect();
+    let Ok(values) = values else {
+        return Some(values);
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token('}') {
+        return Some(Err(ErrorKind::ObjectNotClosed.at(start, toks.location())));
+    }
+
+    Some(Ok(values))
+}
+
+fn object_field(
+    toks: &mut impl Tokens<Item = char>,
+    depth: usize,
+) -> Option<Result<SpannedMember, Error>> {
+    if toks.peek() == Some('}') {
+        return None;
+    }
+    let start = toks.location();
+
+    let name = match string(&mut *toks) {
+        None => return Some(Err(ErrorKind::InvalidObjectField.at(start.clone(), start))),
+        Some(Err(err)) => return Some(Err(err)),
+        Some(Ok(s)) => Spanned::new(s, start.offset()..toks.offset()),
+    };
+
+    skip_whitespace(&mut *toks);
+    if !toks.token(':') {
+        let loc = toks.location();
+        return Some(Err(
+            ErrorKind::MissingObjectFieldSeparator.at(loc.clone(), loc)
+        ));
+    }
+    skip_whitespace(&mut *toks);
+
+    let val = match value(&mut *toks, depth) {
+        Ok(val) => val,
+        Err(e) => return Some(Err(e)),
+    };
+
+    Some(Ok((name, val)))
+}
@@ -17,8 +17,15 @@ def main():
     data = json.loads(args.run.read_text())
     cases = sorted(data["libs"].values(), key=lambda c: (c["crate"] if c["crate"] else "", c["name"]))
 
-    print("Name | Overhead (release) | Build (debug) | Parse (release) | Downloads | Version")
-    print("-----|--------------------|---------------|-----------------|-----------|--------")
+    # Runs from before `count-alloc` have no allocation counts
+    has_alloc = any(case.get("alloc") for case in cases)
+
+    header = ["Name", "Overhead (release)", "Build (debug)", "Parse (release)", "Throughput", "Peak memory"]
+    if has_alloc:
+        header.append("Allocations")
+    header += ["Downloads", "Version"]
+    print(" | ".join(header))
+    print("|".join("-" * (len(h) + 2) for h in header)[1:-1])
     for case in cases:
         if case["name"] != "null":
             count_link = "![Download count](https://img.shields.io/crates/dr/{})".format(case["crate"])
@@ -28,7 +35,13 @@ def main():
             case["name"],
             fmt_size(case, cases[0]),
             fmt_time(case, "build"),
-            fmt_time(case, "run"),
+            fmt_time(case, "run", PRIMARY_DATASET),
+            fmt_throughput(case, PRIMARY_DATASET),
+            fmt_memory(case, PRIMARY_DATASET),
+        ]
+        if has_alloc:
+            row.append(fmt_alloc(case, PRIMARY_DATASET))
+        row += [
             count_link,
             case["version"] if case["version"] else "-",
         ]
@@ -36,13 +49,52 @@ def main():
     print()
     print(f"*System: {data['os']} {data['os_ver']} ({data['arch']}), {data.get('rustc', '')} w/ `-j {data['cpus']}`*")
 
+    datasets = []
+    for case in cases:
+        for result in (case["run"] or {}).get("results", []):
+            dataset = result_dataset(result)
+            if dataset not in datasets:
+                datasets.append(dataset)
+    if len(datasets) > 1:
+        print()
+        print("Name | " + " | ".join(datasets))
+        print("-----|" + "|".join("-" * (len(d) + 2) for d in datasets))
+        for case in cases:
+            row = [case["name"]] + [fmt_dataset(case, dataset) for dataset in datasets]
+            print(" | ".join(row))
+
+    # Only apps with the feature, and only runs that included the phase
+    print_mode_table(cases, "spans", "spanned", "Span cost")
+    print_mode_table(cases, "borrowed", "borrowed", "Borrowing")
+    print_mode_table(cases, "ordered", "ordered", "Key order")
+    print_mode_table(cases, "ordered", "unique-keys", "Unique keys")
+
+PRIMARY_DATASET = "canada"
+CANADA_PATH = pathlib.Path(__file__).parent / "third_party/nativejson-benchmark/data/canada.json"
 
-def fmt_time(case, bench):
+
+def result_dataset(result):
+    # Runs from before datasets were introduced only parsed canada.json
+    return result.get("parameters", {}).get("dataset", PRIMARY_DATASET)
+
+
+def find_result(case, bench, dataset=None):
     bench = case[bench]
     if bench is None:
+        return None
+
+    results = [r for r in bench["results"] if dataset is None or result_dataset(r) == dataset]
+    if not results:
+        return None
+    return results[0]
+
+
+def fmt_time(case, bench, dataset=None):
+    result = find_result(case, bench, dataset)
+    if result is None:
         return "N/A"
 
-    value = bench["results"][0]["median"]
+    value = result["median"]
     if value < 1:
         value *= 1000
         return "{:.0f}ms".format(value)
@@ -50,7 +102,79 @@ def fmt_time(case, bench):
         return "{:.0f}s".format(value)
 
 
+def fmt_dataset(case, dataset):
+    if find_result(case, "run", dataset) is None:
+        return "N/A"
+    return "{} ({})".format(fmt_time(case, "run", dataset), fmt_throughput(case, dataset))
+
+
+def fmt_throughput(case, dataset):
+    result = find_result(case, "run", dataset)
+    if result is None:
+        return "N/A"
+
+    input_bytes = result.get("input_bytes")
+    if input_bytes is None and dataset == PRIMARY_DATASET and CANADA_PATH.exists():
+        # Runs from before input sizes were recorded parsed the vendored canada.json
+        input_bytes = CANADA_PATH.stat().st_size
+    if input_bytes is None:
+        return "N/A"
+    return "{:.1f} MB/s".format(input_bytes / result["median"] / 1_000_000)
+
+
+def fmt_memory(case, dataset):
+    result = find_result(case, "run", dataset)
+    if result is None or not result.get("memory_usage_byte"):
+        return "N/A"
+    # Max RSS barely varies between runs; the largest is the conservative figure
+    return "{:.1f} MiB".format(max(result["memory_usage_byte"]) / 1024 / 1024)
+
+
+def fmt_alloc(case, dataset):
+    alloc = (case.get("alloc") or {}).get(dataset)
+    if alloc is None:
+        return "N/A"
+    return "{:,} ({:.1f} MiB peak)".format(alloc["allocations"], alloc["peak_bytes"] / 1024 / 1024)
+
+
+def print_mode_table(cases, bench, mode, title):
+    cases = [case for case in cases if case.get(bench)]
+    if not cases:
+        return
+
+    datasets = []
+    for case in cases:
+        for result in case[bench]["results"]:
+            dataset = result_dataset(result)
+            if dataset not in datasets:
+                datasets.append(dataset)
+    print()
+    print(f"{title} | " + " | ".join(datasets))
+    print("-" * (len(title) + 1) + "|" + "|".join("-" * (len(d) + 2) for d in datasets))
+    for case in cases:
+        row = [case["name"]] + [fmt_mode_cost(case, bench, mode, dataset) for dataset in datasets]
+        print(" | ".join(row))
+
+
+def find_mode_result(case, bench, dataset, mode):
+    for result in (case.get(bench) or {}).get("results", []):
+        if result_dataset(result) == dataset and result["parameters"].get("mode") == mode:
+            return result
+    return None
+
+
+def fmt_mode_cost(case, bench, mode, dataset):
+    # Relative to `--mode=value` from the same binary
+    value = find_mode_result(case, bench, dataset, "value")
+    other = find_mode_result(case, bench, dataset, mode)
+    if value is None or other is None:
+        return "N/A"
+    return "{:+.0%}".format(other["median"] / value["median"] - 1)
+
+
 def fmt_size(case, null_case):
+    if case["size"] is None or null_case["size"] is None:
+        return "N/A"
     delta = (case["size"] - null_case["size"]) / 1024
     return "{:,.0f} KiB".format(delta)
 
@@ -0,0 +1,564 @@
+//! `cargo xtask bench`: record a `runs/<date>-<host>.json`
+//!
+//! Phases that aren't selected are carried over from an existing run file for the same day and
+//! host, as are apps that aren't selected.
+//!
+//! The `alloc`, `spans`, `borrowed` and `ordered` phases build each app with their feature
+//! (`count-alloc`, `spanned`, `borrowed` and `ordered`) into separate target directories, so the
+//! binaries that are sized and timed stay as they are.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::datasets::{self, Dataset};
+use crate::measure::{measure, Options};
+use crate::runs::{Allocations, Lib, Measurement, Report, Run};
+use crate::{host, repo_root, Error};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Measurements to take
+    #[arg(long = "phase", value_enum, default_values_t = [Phase::Build, Phase::Size, Phase::Run, Phase::Alloc, Phase::Spans, Phase::Borrowed, Phase::Ordered])]
+    phases: Vec<Phase>,
+    /// Apps to measure, by name (e.g. `nom`) [default: every `examples/*-app`]
+    #[arg(long = "app")]
+    apps: Vec<String>,
+    /// Built-in datasets to parse [default: all unless `--input` is given]
+    #[arg(long = "dataset", value_parser = clap::builder::PossibleValuesParser::new(datasets::NAMES))]
+    datasets: Vec<String>,
+    /// Other files to parse, recorded under their file stem
+    #[arg(long = "input")]
+    inputs: Vec<PathBuf>,
+    /// Untimed runs before measuring
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+    /// Minimum number of timed runs
+    #[arg(long, default_value_t = 5)]
+    min_runs: usize,
+    /// Minimum seconds spent on timed runs
+    #[arg(long, default_value_t = 3.0)]
+    min_time: f64,
+    /// Parallelism for debug builds [default: number of CPUs]
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    /// Show the output of the benchmarked commands
+    #[arg(long)]
+    show_output: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+enum Phase {
+    /// Debug build time of the app from a clean target directory
+    Build,
+    /// Release binary size
+    Size,
+    /// Release parse time of each dataset
+    Run,
+    /// Allocations while parsing each dataset, via `count-alloc`
+    Alloc,
+    /// Release parse time of each dataset with and without `--mode=spanned`, for apps that
+    /// have a `spanned` feature
+    Spans,
+    /// Release parse time of each dataset with and without `--mode=borrowed`, for apps that
+    /// have a `borrowed` feature
+    Borrowed,
+    /// Release parse time of each dataset with `--mode=value`, `--mode=ordered` and
+    /// `--mode=unique-keys`, for apps that have an `ordered` feature
+    Ordered,
+}
+
+pub fn run(args: &Args) -> Result<(), Error> {
+    let repo_root = repo_root();
+    let host = host::host();
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let jobs = args.jobs.unwrap_or(cpus);
+
+    let runs_root = repo_root.join("runs");
+    std::fs::create_dir_all(&runs_root)?;
+    let timestamp = host::today();
+    let run_path = runs_root.join(format!("{timestamp}-{}.json", host.hostname));
+    let old_run = if run_path.exists() {
+        Run::load(&run_path)?
+    } else {
+        Run::default()
+    };
+
+    let mut inputs = Vec::new();
+    if [
+        Phase::Run,
+        Phase::Alloc,
+        Phase::Spans,
+        Phase::Borrowed,
+        Phase::Ordered,
+    ]
+    .iter()
+    .any(|phase| args.phases.contains(phase))
+    {
+        let names = if args.datasets.is_empty() && args.inputs.is_empty() {
+            datasets::NAMES.iter().map(|n| n.to_string()).collect()
+        } else {
+            args.datasets.clone()
+        };
+        for name in &names {
+            inputs.push(Dataset::builtin(&repo_root, name)?);
+        }
+        for path in &args.inputs {
+            inputs.push(Dataset::file(path)?);
+        }
+    }
+
+    let apps = select_apps(&repo_root, &args.apps)?;
+    let metadata = Metadata::load(&repo_root)?;
+    let opts = Options {
+        warmup: args.warmup,
+        min_runs: args.min_runs,
+        min_time: Duration::from_secs_f64(args.min_time),
+        show_output: args.show_output,
+    };
+
+    let mut run = Run {
+        timestamp,
+        hostname: host.hostname,
+        os: host.os,
+        os_ver: host.os_ver,
+        arch: host.arch,
+        cpus,
+        rustc: rustc_version()?,
+        libs: if args.apps.is_empty() {
+            BTreeMap::new()
+        } else {
+            old_run.libs.clone()
+        },
+    };
+
+    for app in &apps {
+        let manifest_path = format!("examples/{app}/Cargo.toml");
+        let name = app.rsplit_once('-').map_or(app.as_str(), |(n, _)| n);
+        let old = old_run.libs.get(&manifest_path);
+        let (krate, version) = metadata.benchmarked_crate(app, name);
+        eprintln!("Benchmarking {app}");
+
+        let build = if args.phases.contains(&Phase::Build) {
+            // Doing debug builds because that is more likely the time directly impacting people
+            let build = cargo(["build", "-j", &jobs.to_string(), "--package", app]);
+            let clean = cargo(["clean"]);
+            let measurement = measure(&repo_root, &build, Some(&clean), &opts)?;
+            Some(Report {
+                results: vec![measurement],
+            })
+        } else {
+            old.and_then(|o| o.build.clone())
+        };
+
+        let needs_release = args.phases.contains(&Phase::Size) || args.phases.contains(&Phase::Run);
+        let app_path = format!("target/release/{app}{}", std::env::consts::EXE_SUFFIX);
+        if needs_release {
+            // Doing release builds because that is where size probably matters most
+            let status = Command::new("cargo")
+                .args(["build", "--release", "--package", app])
+                .current_dir(&repo_root)
+                .stdin(Stdio::null())
+                .status()?;
+            if !status.success() {
+                return Err(format!("release build of {app} failed: {status}").into());
+            }
+        }
+
+        let size = if args.phases.contains(&Phase::Size) {
+            Some(repo_root.join(&app_path).metadata()?.len())
+        } else {
+            old.and_then(|o| o.size)
+        };
+
+        let parse = if args.phases.contains(&Phase::Run) {
+            let results = measure_parses(
+                &repo_root,
+                app,
+                std::slice::from_ref(&app_path),
+                &inputs,
+                &[],
+                &opts,
+            )?;
+            Some(Report { results })
+        } else {
+            old.and_then(|o| o.run.clone())
+        };
+
+        let alloc = if args.phases.contains(&Phase::Alloc) {
+            Some(count_allocations(&repo_root, app, &inputs)?)
+        } else {
+            old.and_then(|o| o.alloc.clone())
+        };
+
+        let spans = if !args.phases.contains(&Phase::Spans) {
+            old.and_then(|o| o.spans.clone())
+        } else if metadata.has_feature(app, "spanned") {
+            Some(measure_mode(
+                &repo_root,
+                app,
+                "spanned",
+                &["spanned"],
+                &inputs,
+                &opts,
+            )?)
+        } else {
+            None
+        };
+
+        let borrowed = if !args.phases.contains(&Phase::Borrowed) {
+            old.and_then(|o| o.borrowed.clone())
+        } else if metadata.has_feature(app, "borrowed") {
+            Some(measure_mode(
+                &repo_root,
+                app,
+                "borrowed",
+                &["borrowed"],
+                &inputs,
+                &opts,
+            )?)
+        } else {
+            None
+        };
+
+        let ordered = if !args.phases.contains(&Phase::Ordered) {
+            old.and_then(|o| o.ordered.clone())
+        } else if metadata.has_feature(app, "ordered") {
+            let modes = ["ordered", "unique-keys"];
+            Some(measure_mode(
+                &repo_root, app, "ordered", &modes, &inputs, &opts,
+            )?)
+        } else {
+            None
+        };
+
+        run.libs.insert(
+            manifest_path.clone(),
+            Lib {
+                name: name.to_owned(),
+                manifest_path,
+                krate,
+                version,
+                build,
+                run: parse,
+                size,
+                alloc,
+                spans,
+                borrowed,
+                ordered,
+            },
+        );
+    }
+
+    run.save(&run_path)?;
+    println!("{}", run_path.display());
+    Ok(())
+}
+
+/// Time `command` parsing each input, recording `parameters` alongside the dataset
+fn measure_parses(
+    repo_root: &Path,
+    app: &str,
+    command: &[String],
+    inputs: &[Dataset],
+    parameters: &[(&str, &str)],
+    opts: &Options,
+) -> Result<Vec<Measurement>, Error> {
+    let label = parameters
+        .iter()
+        .map(|(_, value)| format!(" ({value})"))
+        .collect::<String>();
+    let mut results = Vec::new();
+    for input in inputs {
+        let mut argv = command.to_vec();
+        argv.push(input.path.display().to_string());
+        match measure(repo_root, &argv, None, opts) {
+            Ok(mut measurement) => {
+                let dataset = [("dataset".to_owned(), input.name.clone())];
+                let others = parameters
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()));
+                measurement.parameters = Some(dataset.into_iter().chain(others).collect());
+                measurement.input_bytes = Some(input.path.metadata()?.len());
+                if let Some(throughput) = measurement.throughput() {
+                    eprintln!("  {}{label}: {throughput:.1} MB/s", input.name);
+                }
+                results.push(measurement);
+            }
+            // Not every parser handles every dataset; leave a gap rather than abort
+            Err(err) => eprintln!("warning: skipping {} for {app}: {err}", input.name),
+        }
+    }
+    Ok(results)
+}
+
+/// Time each input as `--mode=value` and as each of `modes`, with the app built with `feature`
+///
+/// All modes come from the same binary so the difference is down to the mode alone.
+fn measure_mode(
+    repo_root: &Path,
+    app: &str,
+    feature: &str,
+    modes: &[&str],
+    inputs: &[Dataset],
+    opts: &Options,
+) -> Result<Report, Error> {
+    let target_dir = repo_root.join("target").join(feature);
+    let status = Command::new("cargo")
+        .args([
+            "build",
+            "--release",
+            "--package",
+            app,
+            "--features",
+            feature,
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(repo_root)
+        .stdin(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(format!("{feature} build of {app} failed: {status}").into());
+    }
+
+    let app_path = target_dir.join(format!("release/{app}{}", std::env::consts::EXE_SUFFIX));
+    let mut results = Vec::new();
+    for mode in std::iter::once(&"value").chain(modes) {
+        let command = [app_path.display().to_string(), format!("--mode={mode}")];
+        results.extend(measure_parses(
+            repo_root,
+            app,
+            &command,
+            inputs,
+            &[("mode", mode)],
+            opts,
+        )?);
+    }
+    Ok(Report { results })
+}
+
+/// Parse each input once with the app's `count-alloc` feature enabled
+fn count_allocations(
+    repo_root: &Path,
+    app: &str,
+    inputs: &[Dataset],
+) -> Result<BTreeMap<String, Allocations>, Error> {
+    let target_dir = repo_root.join("target/count-alloc");
+    let status = Command::new("cargo")
+        .args([
+            "build",
+            "--release",
+            "--package",
+            app,
+            "--features",
+            "count-alloc",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(repo_root)
+        .stdin(Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(format!("count-alloc build of {app} failed: {status}").into());
+    }
+
+    let app_path = target_dir.join(format!("release/{app}{}", std::env::consts::EXE_SUFFIX));
+    let report_path = target_dir.join(format!("{app}.json"));
+    let mut allocations = BTreeMap::new();
+    for input in inputs {
+        let _ = std::fs::remove_file(&report_path);
+        let status = Command::new(&app_path)
+            .arg(&input.path)
+            .env("COUNT_ALLOC_REPORT", &report_path)
+            .current_dir(repo_root)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        if !status.success() || !report_path.exists() {
+            eprintln!(
+                "warning: no allocation counts for {} with {app}",
+                input.name
+            );
+            continue;
+        }
+        let raw = std::fs::read_to_string(&report_path)?;
+        let counts: Allocations = serde_json::from_str(&raw)?;
+        eprintln!(
+            "  {}: {} allocations, {:.1} MiB peak",
+            input.name,
+            counts.allocations,
+            counts.peak_bytes as f64 / (1024.0 * 1024.0)
+        );
+        allocations.insert(input.name.clone(), counts);
+    }
+    Ok(allocations)
+}
+
+fn cargo<const N: usize>(args: [&str; N]) -> Vec<String> {
+    std::iter::once("cargo")
+        .chain(args)
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Directory names of the `examples/*-app`s to benchmark
+fn select_apps(repo_root: &Path, names: &[String]) -> Result<Vec<String>, Error> {
+    let mut all = Vec::new();
+    for entry in std::fs::read_dir(repo_root.join("examples"))? {
+        let dir = entry?.file_name().to_string_lossy().into_owned();
+        if dir.ends_with("-app") {
+            all.push(dir);
+        }
+    }
+    all.sort();
+    if names.is_empty() {
+        return Ok(all);
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            let dir = format!("{name}-app");
+            if all.contains(&dir) {
+                Ok(dir)
+            } else {
+                let known = all
+                    .iter()
+                    .map(|a| a.trim_end_matches("-app"))
+                    .collect::<Vec<_>>();
+                Err(format!(
+                    "unknown app `{name}`, expected one of: {}",
+                    known.join(", ")
+                )
+                .into())
+            }
+        })
+        .collect()
+}
+
+fn rustc_version() -> Result<String, Error> {
+    let output = Command::new("rustc").arg("--version").output()?;
+    if !output.status.success() {
+        return Err("`rustc --version` failed".into());
+    }
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
+
+/// The dependency graph, as reported by `cargo metadata`
+struct Metadata {
+    /// Package id to `(name, version, is registry package)`
+    packages: HashMap<String, (String, String, bool)>,
+    /// Package id to non-dev dependency ids, in name order
+    deps: HashMap<String, Vec<String>>,
+    /// Workspace package name to its features
+    features: HashMap<String, Vec<String>>,
+}
+
+impl Metadata {
+    fn load(repo_root: &Path) -> Result<Self, Error> {
+        let output = Command::new("cargo")
+            .args(["metadata", "--format-version", "1"])
+            .current_dir(repo_root)
+            .stderr(Stdio::inherit())
+            .output()?;
+        if !output.status.success() {
+            return Err("`cargo metadata` failed".into());
+        }
+        let raw: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+
+        let mut packages = HashMap::new();
+        let mut features = HashMap::new();
+        for package in raw["packages"].as_array().into_iter().flatten() {
+            let id = package["id"].as_str().unwrap_or_default().to_owned();
+            let name = package["name"].as_str().unwrap_or_default().to_owned();
+            let version = package["version"].as_str().unwrap_or_default().to_owned();
+            let registry = !package["source"].is_null();
+            if !registry {
+                let names = package["features"]
+                    .as_object()
+                    .into_iter()
+                    .flat_map(|f| f.keys().cloned())
+                    .collect();
+                features.insert(name.clone(), names);
+            }
+            packages.insert(id, (name, version, registry));
+        }
+
+        let mut deps = HashMap::new();
+        for node in raw["resolve"]["nodes"].as_array().into_iter().flatten() {
+            let id = node["id"].as_str().unwrap_or_default().to_owned();
+            let mut node_deps = node["deps"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|dep| {
+                    dep["dep_kinds"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .any(|kind| kind["kind"].as_str() != Some("dev"))
+                })
+                .filter_map(|dep| dep["pkg"].as_str().map(str::to_owned))
+                .collect::<Vec<_>>();
+            node_deps.sort_by_key(|dep| packages.get(dep).map(|p| p.0.clone()));
+            deps.insert(id, node_deps);
+        }
+
+        Ok(Self {
+            packages,
+            deps,
+            features,
+        })
+    }
+
+    fn has_feature(&self, app: &str, feature: &str) -> bool {
+        self.features
+            .get(app)
+            .is_some_and(|features| features.iter().any(|f| f == feature))
+    }
+
+    /// The crate an app exists to exercise, with its version (e.g. `v1.0.0`)
+    ///
+    /// This is the dependency named after the app if there is one, otherwise the app's first
+    /// direct registry dependency.
+    fn benchmarked_crate(&self, app: &str, name: &str) -> (Option<String>, Option<String>) {
+        let Some(root) = self
+            .packages
+            .iter()
+            .find_map(|(id, (n, _, registry))| (n == app && !registry).then_some(id))
+        else {
+            return (None, None);
+        };
+
+        let mut seen = HashMap::new();
+        let mut stack = vec![root.clone()];
+        while let Some(id) = stack.pop() {
+            for dep in self.deps.get(&id).into_iter().flatten() {
+                if let Some((dep_name, version, _)) = self.packages.get(dep) {
+                    seen.entry(dep_name.as_str()).or_insert(version.as_str());
+                }
+                stack.push(dep.clone());
+            }
+        }
+
+        let first = self.deps.get(root).into_iter().flatten().find_map(|dep| {
+            let (n, _, registry) = self.packages.get(dep)?;
+            registry.then_some(n.as_str())
+        });
+        let krate = if seen.contains_key(name) {
+            Some(name)
+        } else {
+            first
+        };
+        match krate {
+            Some(krate) => (
+                Some(krate.to_owned()),
+                seen.get(krate).map(|v| format!("v{v}")),
+            ),
+            None => (None, None),
+        }
+    }
+}
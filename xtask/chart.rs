@@ -0,0 +1,390 @@
+//! `cargo xtask chart`: SVG trend lines across `runs/*.json`
+//!
+//! Timings from different hosts and toolchains aren't directly comparable, so by default each
+//! point is divided by the baseline app's timing from the same run.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::compare::all_runs;
+use crate::datasets;
+use crate::host::{civil_from_days, day_number, days_from_civil};
+use crate::runs::{Lib, Run};
+use crate::{repo_root, Error};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory to write the charts to [default: target/charts]
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// App whose timings, from the same run, the others are divided by
+    #[arg(long, default_value = "null")]
+    baseline: String,
+    /// Plot raw timings instead of normalizing by `--baseline`
+    #[arg(long)]
+    absolute: bool,
+}
+
+pub fn run(args: &Args) -> Result<(), Error> {
+    let repo_root = repo_root();
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| repo_root.join("target/charts"));
+    let runs = all_runs(&repo_root.join("runs"))?
+        .iter()
+        .map(|p| Run::load(p))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut names = runs
+        .iter()
+        .flat_map(|run| run.libs.values().map(|lib| lib.name.clone()))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+
+    for metric in [Metric::Build, Metric::Overhead, Metric::Parse] {
+        let normalize = (!args.absolute && metric != Metric::Overhead).then_some(&*args.baseline);
+        let all_series = names
+            .iter()
+            .map(|name| Series::collect(&runs, name, metric, normalize))
+            .filter(|series| !series.points.is_empty())
+            .collect::<Vec<_>>();
+        let axis = Axis::new(metric, normalize);
+
+        let dir = out.join(metric.slug());
+        std::fs::create_dir_all(&dir)?;
+        for series in &all_series {
+            let title = format!("{}: {}", series.name, metric.title());
+            let svg = render(&title, &axis, &runs, std::slice::from_ref(series), true);
+            write(&dir.join(format!("{}.svg", series.name)), &svg)?;
+        }
+        let svg = render(metric.title(), &axis, &runs, &all_series, false);
+        write(&out.join(format!("{}.svg", metric.slug())), &svg)?;
+    }
+
+    println!("{}", out.display());
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    std::fs::write(path, contents).map_err(|e| format!("{}: {e}", path.display()).into())
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Metric {
+    Build,
+    Overhead,
+    Parse,
+}
+
+impl Metric {
+    fn slug(self) -> &'static str {
+        match self {
+            Self::Build => "build",
+            Self::Overhead => "overhead",
+            Self::Parse => "parse",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Build => "Build (debug)",
+            Self::Overhead => "Overhead (release)",
+            Self::Parse => "Parse (release)",
+        }
+    }
+
+    /// Seconds for timings, KiB for overhead
+    fn value(self, run: &Run, lib: &Lib) -> Option<f64> {
+        match self {
+            Self::Build => Some(lib.build.as_ref()?.results.first()?.median),
+            Self::Parse => Some(lib.run.as_ref()?.dataset(datasets::PRIMARY)?.median),
+            Self::Overhead => {
+                let null = find(run, "null")?.size?;
+                Some(lib.size?.saturating_sub(null) as f64 / 1024.0)
+            }
+        }
+    }
+}
+
+fn find<'r>(run: &'r Run, name: &str) -> Option<&'r Lib> {
+    run.libs.values().find(|lib| lib.name == name)
+}
+
+struct Series {
+    name: String,
+    points: Vec<Point>,
+}
+
+struct Point {
+    /// Index into the runs
+    run: usize,
+    day: i64,
+    value: f64,
+    /// Set when the benchmarked crate's version differs from the previous point
+    new_version: Option<String>,
+}
+
+impl Series {
+    fn collect(runs: &[Run], name: &str, metric: Metric, normalize: Option<&str>) -> Self {
+        let mut points = Vec::new();
+        let mut last_version = None;
+        for (i, run) in runs.iter().enumerate() {
+            let Some(lib) = find(run, name) else {
+                continue;
+            };
+            let Some(mut value) = metric.value(run, lib) else {
+                continue;
+            };
+            if let Some(baseline) = normalize {
+                match find(run, baseline).and_then(|b| metric.value(run, b)) {
+                    Some(base) if base > 0.0 => value /= base,
+                    _ => continue,
+                }
+            }
+            let version = lib.version.clone();
+            let new_version = (version.is_some() && version != last_version)
+                .then(|| version.clone().unwrap_or_default());
+            last_version = version;
+            points.push(Point {
+                run: i,
+                day: day_number(&run.timestamp),
+                value,
+                new_version,
+            });
+        }
+        Self {
+            name: name.to_owned(),
+            points,
+        }
+    }
+}
+
+/// How to label the y axis
+struct Axis {
+    label: String,
+    fmt: fn(f64) -> String,
+}
+
+impl Axis {
+    fn new(metric: Metric, normalize: Option<&str>) -> Self {
+        match (metric, normalize) {
+            (Metric::Overhead, _) => Self {
+                label: "KiB over `null`".to_owned(),
+                fmt: |v| format!("{v:.0} KiB"),
+            },
+            (_, Some(baseline)) => Self {
+                label: format!("× `{baseline}` in the same run"),
+                fmt: |v| format!("{v:.1}×"),
+            },
+            (_, None) => Self {
+                label: "median".to_owned(),
+                fmt: |v| {
+                    if v < 1.0 {
+                        format!("{:.0}ms", v * 1000.0)
+                    } else {
+                        format!("{v:.1}s")
+                    }
+                },
+            },
+        }
+    }
+}
+
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 450.0;
+const LEFT: f64 = 70.0;
+const TOP: f64 = 40.0;
+const BOTTOM: f64 = 50.0;
+const PALETTE: &[&str] = &[
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf", "#393b79", "#637939", "#8c6d31", "#843c39", "#7b4173",
+];
+
+/// Draw `series` as lines over time, with host switches marked as dividers
+///
+/// `annotate` labels every version change, which is too busy for multi-series charts.
+fn render(title: &str, axis: &Axis, runs: &[Run], series: &[Series], annotate: bool) -> String {
+    let right = if annotate { 30.0 } else { 130.0 };
+    let plot_w = WIDTH - LEFT - right;
+    let plot_h = HEIGHT - TOP - BOTTOM;
+
+    let points = || series.iter().flat_map(|s| s.points.iter());
+    let first_day = points().map(|p| p.day).min().unwrap_or(0);
+    let last_day = points()
+        .map(|p| p.day)
+        .max()
+        .unwrap_or(0)
+        .max(first_day + 1);
+    let (y_max, y_step) = nice_scale(points().map(|p| p.value).fold(0.0, f64::max));
+    let x = |day: i64| LEFT + (day - first_day) as f64 / (last_day - first_day) as f64 * plot_w;
+    let y = |value: f64| TOP + plot_h - value / y_max * plot_h;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="20" font-size="15" text-anchor="middle">{}</text>"#,
+        WIDTH / 2.0,
+        escape(title)
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text transform="translate(14 {}) rotate(-90)" text-anchor="middle">{}</text>"#,
+        TOP + plot_h / 2.0,
+        escape(&axis.label)
+    );
+
+    // Y grid
+    let mut tick = 0.0;
+    while tick <= y_max + y_step / 2.0 {
+        let ty = y(tick);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{LEFT}" y1="{ty:.1}" x2="{:.1}" y2="{ty:.1}" stroke="#ddd"/><text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"##,
+            LEFT + plot_w,
+            LEFT - 6.0,
+            ty + 4.0,
+            escape(&(axis.fmt)(tick))
+        );
+        tick += y_step;
+    }
+
+    // X axis, labeled by year
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{LEFT}" y1="{0:.1}" x2="{1:.1}" y2="{0:.1}" stroke="black"/>"#,
+        TOP + plot_h,
+        LEFT + plot_w
+    );
+    let (first_year, _, _) = civil_from_days(first_day);
+    let (last_year, _, _) = civil_from_days(last_day);
+    for year in first_year..=last_year + 1 {
+        let day = days_from_civil(year, 1, 1);
+        if !(first_day..=last_day).contains(&day) {
+            continue;
+        }
+        let tx = x(day);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{tx:.1}" y1="{0:.1}" x2="{tx:.1}" y2="{1:.1}" stroke="black"/><text x="{tx:.1}" y="{2:.1}" text-anchor="middle">{year}</text>"#,
+            TOP + plot_h,
+            TOP + plot_h + 5.0,
+            TOP + plot_h + 18.0
+        );
+    }
+
+    // Host switches, among the runs that are plotted
+    let mut last_host = None;
+    for run in points()
+        .map(|p| p.run)
+        .collect::<std::collections::BTreeSet<_>>()
+    {
+        let run = &runs[run];
+        if last_host.is_some_and(|h| h != run.hostname) {
+            let tx = x(day_number(&run.timestamp));
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{tx:.1}" y1="{TOP}" x2="{tx:.1}" y2="{0:.1}" stroke="#999" stroke-dasharray="4 3"/><text x="{1:.1}" y="{2:.1}" fill="#666">{3}</text>"##,
+                TOP + plot_h,
+                tx + 3.0,
+                TOP + plot_h - 4.0,
+                escape(&format!("{} ({} cpus)", run.hostname, run.cpus))
+            );
+        }
+        last_host = Some(run.hostname.as_str());
+    }
+
+    for (i, s) in series.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let path = s
+            .points
+            .iter()
+            .map(|p| format!("{:.1},{:.1}", x(p.day), y(p.value)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{path}" fill="none" stroke="{color}" stroke-width="1.5"/>"#
+        );
+
+        for p in &s.points {
+            let run = &runs[p.run];
+            let (px, py) = (x(p.day), y(p.value));
+            let version = find(run, &s.name).and_then(|lib| lib.version.as_deref());
+            let tooltip = format!(
+                "{} {}: {} ({}{}, {})",
+                run.timestamp,
+                s.name,
+                (axis.fmt)(p.value),
+                version.unwrap_or("-"),
+                if p.new_version.is_some() { " new" } else { "" },
+                if run.rustc.is_empty() {
+                    "rustc ?"
+                } else {
+                    &run.rustc
+                }
+            );
+            let r = if p.new_version.is_some() { 4.0 } else { 2.5 };
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{px:.1}" cy="{py:.1}" r="{r}" fill="{color}"><title>{}</title></circle>"#,
+                escape(&tooltip)
+            );
+            if annotate {
+                if let Some(version) = &p.new_version {
+                    let _ = writeln!(
+                        svg,
+                        r#"<text transform="translate({:.1} {:.1}) rotate(-35)" fill="{color}">{}</text>"#,
+                        px + 3.0,
+                        py - 6.0,
+                        escape(version)
+                    );
+                }
+            }
+        }
+
+        if !annotate {
+            let ly = TOP + 14.0 * i as f64;
+            let lx = LEFT + plot_w + 12.0;
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{lx:.1}" y1="{ly:.1}" x2="{:.1}" y2="{ly:.1}" stroke="{color}" stroke-width="2"/><text x="{:.1}" y="{:.1}">{}</text>"#,
+                lx + 16.0,
+                lx + 20.0,
+                ly + 4.0,
+                escape(&s.name)
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Round `max` up to a multiple of a 1/2/5 step, returning both
+fn nice_scale(max: f64) -> (f64, f64) {
+    if max <= 0.0 || !max.is_finite() {
+        return (1.0, 0.2);
+    }
+    let raw = max / 5.0;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10.0 * magnitude);
+    ((max / step).ceil() * step, step)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
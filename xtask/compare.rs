@@ -0,0 +1,291 @@
+//! `cargo xtask compare`: report per-parser changes between run files
+//!
+//! Timings are compared with Welch's t-test over each run's mean, stddev and run count; a change
+//! is only flagged when it is both significant and larger than `--threshold`. The change shown and
+//! judged is between the same means, so a skewed sample can't pass the test on one statistic and
+//! the threshold on another.
+
+use std::path::{Path, PathBuf};
+
+use crate::datasets;
+use crate::runs::{Lib, Report, Run};
+use crate::stats::{welch_t_test, Summary};
+use crate::{repo_root, Error};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Run files to compare, oldest first; each is compared against the one before it
+    /// [default: the two most recent in `runs/`]
+    runs: Vec<PathBuf>,
+    /// p-value below which a change in timing is considered significant
+    #[arg(long, default_value_t = 0.05)]
+    alpha: f64,
+    /// Ignore changes smaller than this percentage, even when significant
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+    /// Exit with an error if any regression is flagged
+    #[arg(long)]
+    check: bool,
+}
+
+pub fn run(args: &Args) -> Result<(), Error> {
+    let paths = if args.runs.is_empty() {
+        let all = all_runs(&repo_root().join("runs"))?;
+        all[all.len().saturating_sub(2)..].to_vec()
+    } else {
+        args.runs.clone()
+    };
+    if paths.len() < 2 {
+        return Err("need at least two run files to compare".into());
+    }
+    let runs = paths
+        .iter()
+        .map(|p| Run::load(p))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut regressions = Vec::new();
+    for (i, pair) in runs.windows(2).enumerate() {
+        if i != 0 {
+            println!();
+        }
+        println!("# {} → {}", stem(&paths[i]), stem(&paths[i + 1]));
+        println!();
+        let new = stem(&paths[i + 1]);
+        regressions.extend(
+            compare(&pair[0], &pair[1], args)
+                .into_iter()
+                .map(|r| format!("{new}: {r}")),
+        );
+    }
+
+    println!();
+    if regressions.is_empty() {
+        println!("No regressions");
+    } else {
+        println!("Regressions:");
+        for regression in &regressions {
+            println!("- {regression}");
+        }
+    }
+
+    if args.check && !regressions.is_empty() {
+        return Err(format!("{} regression(s) found", regressions.len()).into());
+    }
+    Ok(())
+}
+
+/// `runs/*.json`, oldest first
+pub fn all_runs(runs_root: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(runs_root)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Print a table of changes from `old` to `new`, returning the regressions
+fn compare(old: &Run, new: &Run, args: &Args) -> Vec<String> {
+    for (label, run) in [("Old", old), ("New", new)] {
+        println!(
+            "*{label}: {} {} ({}), {} w/ `-j {}`*",
+            run.os, run.os_ver, run.arch, run.rustc, run.cpus
+        );
+    }
+    if (&old.hostname, old.cpus) != (&new.hostname, new.cpus) {
+        println!();
+        println!("*Warning: runs are from different hosts, timings may not be comparable*");
+    }
+    println!();
+    println!("*Timings are means, which the p-values test*");
+    println!();
+
+    println!("Name | Overhead (release) | Build (debug) | Parse (release)");
+    println!("-----|--------------------|---------------|----------------");
+
+    let mut names = old
+        .libs
+        .values()
+        .chain(new.libs.values())
+        .map(|lib| lib.name.as_str())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut regressions = Vec::new();
+    for name in names {
+        let old_lib = find(old, name);
+        let new_lib = find(new, name);
+        let mut row = vec![name.to_owned()];
+
+        let overhead = Change::overhead(old, old_lib, new, new_lib, args);
+        row.push(overhead.cell(fmt_size));
+        let build = Change::timing(old_lib, new_lib, |lib| lib.build.as_ref(), args);
+        row.push(build.cell(fmt_time));
+        let parse = Change::timing(old_lib, new_lib, |lib| lib.run.as_ref(), args);
+        row.push(parse.cell(fmt_time));
+        println!("{}", row.join(" | "));
+
+        for (metric, change, fmt) in [
+            ("overhead", overhead, fmt_size as fn(f64) -> String),
+            ("build", build, fmt_time),
+            ("parse", parse, fmt_time),
+        ] {
+            if let Change::Compared {
+                old,
+                new,
+                verdict: Verdict::Regressed,
+                ..
+            } = change
+            {
+                regressions.push(format!(
+                    "{name} {metric}: {} → {} ({:+.1}%)",
+                    fmt(old),
+                    fmt(new),
+                    percent(old, new)
+                ));
+            }
+        }
+    }
+    regressions
+}
+
+fn find<'r>(run: &'r Run, name: &str) -> Option<&'r Lib> {
+    run.libs.values().find(|lib| lib.name == name)
+}
+
+enum Change {
+    Missing,
+    Compared {
+        old: f64,
+        new: f64,
+        p_value: Option<f64>,
+        verdict: Verdict,
+    },
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Verdict {
+    Unchanged,
+    Improved,
+    Regressed,
+}
+
+impl Change {
+    /// Release binary size over `null`'s, which doesn't vary between builds
+    fn overhead(
+        old_run: &Run,
+        old: Option<&Lib>,
+        new_run: &Run,
+        new: Option<&Lib>,
+        args: &Args,
+    ) -> Self {
+        let overhead = |run: &Run, lib: Option<&Lib>| {
+            let null = find(run, "null")?.size?;
+            Some(lib?.size?.saturating_sub(null) as f64)
+        };
+        match (overhead(old_run, old), overhead(new_run, new)) {
+            (Some(old), Some(new)) => Self::Compared {
+                old,
+                new,
+                p_value: None,
+                verdict: verdict(old, new, true, args),
+            },
+            _ => Self::Missing,
+        }
+    }
+
+    /// Mean time of the primary dataset in each report
+    fn timing(
+        old: Option<&Lib>,
+        new: Option<&Lib>,
+        report: impl Fn(&Lib) -> Option<&Report>,
+        args: &Args,
+    ) -> Self {
+        let old = old
+            .and_then(&report)
+            .and_then(|r| r.dataset(datasets::PRIMARY));
+        let new = new
+            .and_then(&report)
+            .and_then(|r| r.dataset(datasets::PRIMARY));
+        let (Some(old), Some(new)) = (old, new) else {
+            return Self::Missing;
+        };
+        let p_value = welch_t_test(Summary::from(old), Summary::from(new));
+        let significant = p_value.is_some_and(|p| p < args.alpha);
+        Self::Compared {
+            old: old.mean,
+            new: new.mean,
+            p_value,
+            verdict: verdict(old.mean, new.mean, significant, args),
+        }
+    }
+
+    fn cell(&self, fmt: fn(f64) -> String) -> String {
+        let Self::Compared {
+            old,
+            new,
+            p_value,
+            verdict,
+        } = *self
+        else {
+            return "N/A".to_owned();
+        };
+        let mut cell = format!("{} → {} ({:+.1}%", fmt(old), fmt(new), percent(old, new));
+        if let Some(p) = p_value {
+            cell.push_str(&format!(", p={p:.3}"));
+        }
+        cell.push(')');
+        match verdict {
+            Verdict::Unchanged => {}
+            Verdict::Improved => cell.push_str(" *improved*"),
+            Verdict::Regressed => cell.push_str(" **regressed**"),
+        }
+        cell
+    }
+}
+
+fn verdict(old: f64, new: f64, significant: bool, args: &Args) -> Verdict {
+    let percent = percent(old, new);
+    if !significant || percent.abs() < args.threshold {
+        Verdict::Unchanged
+    } else if percent > 0.0 {
+        Verdict::Regressed
+    } else {
+        Verdict::Improved
+    }
+}
+
+fn percent(old: f64, new: f64) -> f64 {
+    if old == 0.0 {
+        if new == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (new - old) / old * 100.0
+    }
+}
+
+fn fmt_time(secs: f64) -> String {
+    if secs < 1.0 {
+        format!("{:.1}ms", secs * 1000.0)
+    } else {
+        format!("{secs:.2}s")
+    }
+}
+
+fn fmt_size(bytes: f64) -> String {
+    format!("{:.0} KiB", bytes / 1024.0)
+}
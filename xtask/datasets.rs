@@ -0,0 +1,133 @@
+//! Inputs for the parse benchmark
+//!
+//! `canada` is number-heavy and nearly string-free, so the other datasets stress what it doesn't.
+//! They are generated by `json-gen` into `target/datasets/` rather than vendored; each is roughly
+//! the same size as `canada.json`.
+
+use std::path::{Path, PathBuf};
+
+use json_gen::{Config, Weights};
+
+use crate::Error;
+
+/// The dataset `format.py`'s "Parse" column and the other reports use
+pub const PRIMARY: &str = "canada";
+
+pub const NAMES: &[&str] = &[PRIMARY, "strings", "nested", "objects", "unicode"];
+
+const TARGET_LEN: usize = 2 * 1024 * 1024;
+
+pub struct Dataset {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Dataset {
+    /// One of [`NAMES`], generated into `target/datasets/` unless vendored
+    pub fn builtin(repo_root: &Path, name: &str) -> Result<Self, Error> {
+        let path = if name == PRIMARY {
+            repo_root.join("third_party/nativejson-benchmark/data/canada.json")
+        } else {
+            let generate = match name {
+                "strings" => strings,
+                "nested" => nested,
+                "objects" => objects,
+                "unicode" => unicode,
+                _ => {
+                    return Err(format!(
+                        "unknown dataset `{name}`, expected one of: {}",
+                        NAMES.join(", ")
+                    )
+                    .into())
+                }
+            };
+            let dir = repo_root.join("target/datasets");
+            std::fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("{name}.json"));
+            std::fs::write(&path, generate())?;
+            path
+        };
+        Ok(Self {
+            name: name.to_owned(),
+            path,
+        })
+    }
+
+    /// An arbitrary file, named after its stem
+    pub fn file(path: &Path) -> Result<Self, Error> {
+        let path = std::path::absolute(path)?;
+        if !path.exists() {
+            return Err(format!("{}: input does not exist", path.display()).into());
+        }
+        let name = path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .into_owned();
+        Ok(Self { name, path })
+    }
+}
+
+fn strings() -> String {
+    json_gen::generate(&Config {
+        size: TARGET_LEN,
+        depth: 0,
+        string_len: 200,
+        escape_ratio: 0.1,
+        unicode_ratio: 0.0,
+        weights: Weights { string: 1, ..NONE },
+        ..Config::default()
+    })
+}
+
+fn nested() -> String {
+    json_gen::generate(&Config {
+        size: TARGET_LEN,
+        depth: 64,
+        min_breadth: 1,
+        breadth: 1,
+        weights: Weights {
+            null: 1,
+            array: 50,
+            object: 50,
+            ..NONE
+        },
+        ..Config::default()
+    })
+}
+
+fn objects() -> String {
+    json_gen::generate(&Config {
+        size: TARGET_LEN,
+        depth: 2,
+        min_breadth: 4,
+        breadth: 12,
+        weights: Weights {
+            object: 10,
+            array: 1,
+            ..Weights::default()
+        },
+        ..Config::default()
+    })
+}
+
+fn unicode() -> String {
+    json_gen::generate(&Config {
+        size: TARGET_LEN,
+        depth: 0,
+        string_len: 60,
+        escape_ratio: 0.05,
+        unicode_ratio: 0.8,
+        weights: Weights { string: 1, ..NONE },
+        ..Config::default()
+    })
+}
+
+const NONE: Weights = Weights {
+    null: 0,
+    bool: 0,
+    number: 0,
+    string: 0,
+    array: 0,
+    object: 0,
+};
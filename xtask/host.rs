@@ -0,0 +1,132 @@
+//! Details about the machine a run was recorded on
+
+pub struct Host {
+    pub hostname: String,
+    pub os: String,
+    pub os_ver: String,
+    pub arch: String,
+}
+
+#[cfg(unix)]
+pub fn host() -> Host {
+    fn field(raw: &[libc::c_char]) -> String {
+        // SAFETY: `uname` NUL-terminates every field
+        let raw = unsafe { std::ffi::CStr::from_ptr(raw.as_ptr()) };
+        raw.to_string_lossy().into_owned()
+    }
+
+    // SAFETY: `utsname` is plain-old-data and `uname` fills it in
+    let mut uts = unsafe { std::mem::zeroed::<libc::utsname>() };
+    if unsafe { libc::uname(&mut uts) } != 0 {
+        return fallback();
+    }
+    Host {
+        hostname: field(&uts.nodename),
+        os: field(&uts.sysname),
+        os_ver: field(&uts.release),
+        arch: field(&uts.machine),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn host() -> Host {
+    fallback()
+}
+
+fn fallback() -> Host {
+    Host {
+        hostname: std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| "unknown".to_owned()),
+        os: std::env::consts::OS.to_owned(),
+        os_ver: String::new(),
+        arch: std::env::consts::ARCH.to_owned(),
+    }
+}
+
+/// Today's date as `YYYY-MM-DD`, in local time where available
+pub fn today() -> String {
+    let (year, month, day) = local_date();
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(unix)]
+fn local_date() -> (i64, u32, u32) {
+    // SAFETY: `tm` is plain-old-data and `localtime_r` fills it in
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm = std::mem::zeroed::<libc::tm>();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return utc_date();
+        }
+        (
+            i64::from(tm.tm_year) + 1900,
+            (tm.tm_mon + 1) as u32,
+            tm.tm_mday as u32,
+        )
+    }
+}
+
+#[cfg(not(unix))]
+fn local_date() -> (i64, u32, u32) {
+    utc_date()
+}
+
+fn utc_date() -> (i64, u32, u32) {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    civil_from_days((secs / 86_400) as i64)
+}
+
+/// Days since the epoch of a `YYYY-MM-DD` timestamp
+pub fn day_number(timestamp: &str) -> i64 {
+    let mut parts = timestamp
+        .splitn(3, '-')
+        .map(|p| p.parse::<i64>().unwrap_or(1));
+    let year = parts.next().unwrap_or(1970);
+    let month = parts.next().unwrap_or(1);
+    let day = parts.next().unwrap_or(1);
+    days_from_civil(year, month, day)
+}
+
+/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_round_trip() {
+        assert_eq!(day_number("1970-01-01"), 0);
+        assert_eq!(day_number("2024-02-29"), 19_782);
+        for days in [-1, 0, 59, 19_782, 20_558] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, i64::from(m), i64::from(d)), days);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Repo automation, run as `cargo xtask <command>`
+
+mod bench;
+mod chart;
+mod compare;
+mod datasets;
+mod host;
+mod measure;
+mod runs;
+mod stats;
+
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Parser)]
+#[command(name = "xtask")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Measure build time, binary size and parse time of the example apps
+    Bench(bench::Args),
+    /// Report per-parser changes between run files
+    Compare(compare::Args),
+    /// Draw SVG trend charts from every run file
+    Chart(chart::Args),
+}
+
+fn main() -> Result<(), Error> {
+    match Cli::parse().command {
+        Command::Bench(args) => bench::run(&args),
+        Command::Compare(args) => compare::run(&args),
+        Command::Chart(args) => chart::run(&args),
+    }
+}
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives in the repo root")
+        .to_owned()
+}
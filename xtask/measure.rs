@@ -0,0 +1,168 @@
+//! A minimal stand-in for `hyperfine`, reporting in its `--export-json` shape
+
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::runs::Measurement;
+use crate::Error;
+
+pub struct Options {
+    /// Untimed runs before measuring
+    pub warmup: usize,
+    /// Keep measuring until both this many runs ...
+    pub min_runs: usize,
+    /// ... and this much wall time have been spent
+    pub min_time: Duration,
+    /// Pass the command's stdout/stderr through
+    pub show_output: bool,
+}
+
+/// Time `argv`, running `prepare` (untimed) before every run
+pub fn measure(
+    cwd: &Path,
+    argv: &[String],
+    prepare: Option<&[String]>,
+    opts: &Options,
+) -> Result<Measurement, Error> {
+    for _ in 0..opts.warmup {
+        if let Some(prepare) = prepare {
+            run_once(cwd, prepare, opts.show_output)?;
+        }
+        run_once(cwd, argv, opts.show_output)?;
+    }
+
+    let mut samples = Vec::new();
+    let mut spent = Duration::ZERO;
+    while samples.len() < opts.min_runs.max(1) || spent < opts.min_time {
+        if let Some(prepare) = prepare {
+            run_once(cwd, prepare, opts.show_output)?;
+        }
+        let sample = run_once(cwd, argv, opts.show_output)?;
+        spent += sample.wall;
+        samples.push(sample);
+    }
+
+    Ok(summarize(argv.join(" "), &samples))
+}
+
+struct Sample {
+    wall: Duration,
+    user: Duration,
+    system: Duration,
+    /// Peak resident set size, in bytes
+    max_rss: Option<u64>,
+    exit_code: Option<i32>,
+}
+
+fn run_once(cwd: &Path, argv: &[String], show_output: bool) -> Result<Sample, Error> {
+    let (program, args) = argv.split_first().expect("non-empty command");
+    let mut cmd = Command::new(program);
+    cmd.args(args).current_dir(cwd).stdin(Stdio::null());
+    if !show_output {
+        cmd.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+
+    let start = Instant::now();
+    let (status, usage) = cmd
+        .spawn()
+        .and_then(wait)
+        .map_err(|e| format!("`{}`: {e}", argv.join(" ")))?;
+    let wall = start.elapsed();
+
+    if !status.success() {
+        return Err(format!("`{}` failed: {status}", argv.join(" ")).into());
+    }
+    Ok(Sample {
+        wall,
+        user: usage.user,
+        system: usage.system,
+        max_rss: usage.max_rss,
+        exit_code: status.code(),
+    })
+}
+
+#[derive(Default)]
+struct Usage {
+    user: Duration,
+    system: Duration,
+    max_rss: Option<u64>,
+}
+
+/// Reap `child` with `wait4` for its own resource usage
+#[cfg(unix)]
+fn wait(child: Child) -> std::io::Result<(ExitStatus, Usage)> {
+    use std::os::unix::process::ExitStatusExt as _;
+
+    fn to_duration(tv: libc::timeval) -> Duration {
+        Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1_000)
+    }
+
+    let pid = child.id() as libc::pid_t;
+    let mut status = 0;
+    // SAFETY: `rusage` is plain-old-data and `wait4` fills it in
+    let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+    loop {
+        if unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } == pid {
+            break;
+        }
+        let err = std::io::Error::last_os_error();
+        if err.kind() != std::io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+
+    // Linux reports kibibytes, macOS bytes.  Linux also carries the spawning process's peak over
+    // `exec`, so this has a floor of our own footprint; compare against `null`.
+    let rss_unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+    let usage = Usage {
+        user: to_duration(usage.ru_utime),
+        system: to_duration(usage.ru_stime),
+        max_rss: Some(usage.ru_maxrss as u64 * rss_unit),
+    };
+    Ok((ExitStatus::from_raw(status), usage))
+}
+
+#[cfg(not(unix))]
+fn wait(mut child: Child) -> std::io::Result<(ExitStatus, Usage)> {
+    Ok((child.wait()?, Usage::default()))
+}
+
+fn summarize(command: String, samples: &[Sample]) -> Measurement {
+    let times: Vec<f64> = samples.iter().map(|s| s.wall.as_secs_f64()).collect();
+    let mean = average(&times);
+    let stddev = (times.len() > 1).then(|| {
+        let var = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (times.len() - 1) as f64;
+        var.sqrt()
+    });
+    let mut sorted = times.clone();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    };
+
+    let user: Vec<f64> = samples.iter().map(|s| s.user.as_secs_f64()).collect();
+    let system: Vec<f64> = samples.iter().map(|s| s.system.as_secs_f64()).collect();
+    Measurement {
+        command,
+        mean,
+        stddev,
+        median,
+        user: average(&user),
+        system: average(&system),
+        min: sorted[0],
+        max: sorted[sorted.len() - 1],
+        times,
+        memory_usage_byte: samples.iter().map(|s| s.max_rss).collect(),
+        exit_codes: samples.iter().map(|s| s.exit_code).collect(),
+        parameters: None,
+        input_bytes: None,
+    }
+}
+
+fn average(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
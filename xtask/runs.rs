@@ -0,0 +1,127 @@
+//! The `runs/<date>-<host>.json` format
+//!
+//! Field order and names match what `bench.py` + `hyperfine` wrote so older runs stay readable.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{datasets, Error};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Run {
+    pub timestamp: String,
+    pub hostname: String,
+    pub os: String,
+    pub os_ver: String,
+    pub arch: String,
+    pub cpus: usize,
+    #[serde(default)]
+    pub rustc: String,
+    /// Keyed by the app's manifest path, relative to the repo root
+    pub libs: BTreeMap<String, Lib>,
+}
+
+impl Run {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let raw = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        serde_json::from_str(&raw).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let raw = serde_json::to_string_pretty(self)?;
+        fs::write(path, raw).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Lib {
+    pub name: String,
+    pub manifest_path: String,
+    /// The crate the app is benchmarking, `None` for `null-app`
+    #[serde(rename = "crate")]
+    pub krate: Option<String>,
+    pub version: Option<String>,
+    pub build: Option<Report>,
+    pub run: Option<Report>,
+    pub size: Option<u64>,
+    /// `count-alloc` results, keyed by dataset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<BTreeMap<String, Allocations>>,
+    /// Parses by a `spanned` build, with `{"mode": "value" | "spanned"}` in the parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spans: Option<Report>,
+    /// Parses by a `borrowed` build, with `{"mode": "value" | "borrowed"}` in the parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub borrowed: Option<Report>,
+    /// Parses by an `ordered` build, with `{"mode": "value" | "ordered" | "unique-keys"}` in the
+    /// parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ordered: Option<Report>,
+}
+
+/// What `count-alloc` reports for one parse
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Allocations {
+    /// Number of allocations, including reallocations
+    pub allocations: u64,
+    /// Total bytes requested
+    pub bytes: u64,
+    /// Most bytes live at once
+    pub peak_bytes: u64,
+}
+
+/// One `hyperfine --export-json` report
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub results: Vec<Measurement>,
+}
+
+impl Report {
+    /// The parse of `dataset`
+    pub fn dataset(&self, dataset: &str) -> Option<&Measurement> {
+        self.results.iter().find(|m| m.dataset() == dataset)
+    }
+}
+
+/// Timings for one command, in seconds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Measurement {
+    pub command: String,
+    pub mean: f64,
+    pub stddev: Option<f64>,
+    pub median: f64,
+    pub user: f64,
+    pub system: f64,
+    pub min: f64,
+    pub max: f64,
+    pub times: Vec<f64>,
+    /// Peak resident memory of each run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_usage_byte: Option<Vec<u64>>,
+    pub exit_codes: Vec<Option<i32>>,
+    /// `{"dataset": <name>}` for parse measurements, plus the `mode` for [`Lib::spans`],
+    /// [`Lib::borrowed`] and [`Lib::ordered`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<BTreeMap<String, String>>,
+    /// Size of the parsed file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_bytes: Option<u64>,
+}
+
+impl Measurement {
+    /// Runs from before datasets were introduced only parsed `canada`
+    pub fn dataset(&self) -> &str {
+        self.parameters
+            .as_ref()
+            .and_then(|p| p.get("dataset"))
+            .map_or(datasets::PRIMARY, String::as_str)
+    }
+
+    /// Megabytes (10^6 bytes) parsed per second, by the median time
+    pub fn throughput(&self) -> Option<f64> {
+        Some(self.input_bytes? as f64 / self.median / 1_000_000.0)
+    }
+}
@@ -0,0 +1,147 @@
+//! Just enough statistics to tell noise from a real change
+
+use crate::runs::Measurement;
+
+/// Summary of repeated timings of one command
+#[derive(Copy, Clone, Debug)]
+pub struct Summary {
+    pub runs: usize,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl From<&Measurement> for Summary {
+    fn from(m: &Measurement) -> Self {
+        Self {
+            runs: m.times.len(),
+            mean: m.mean,
+            stddev: m.stddev.unwrap_or(0.0),
+        }
+    }
+}
+
+/// Two-sided p-value of Welch's t-test that `a` and `b` have the same mean
+///
+/// Returns `None` when there aren't enough runs to estimate variance.
+pub fn welch_t_test(a: Summary, b: Summary) -> Option<f64> {
+    if a.runs < 2 || b.runs < 2 {
+        return None;
+    }
+    let va = a.stddev.powi(2) / a.runs as f64;
+    let vb = b.stddev.powi(2) / b.runs as f64;
+    let se2 = va + vb;
+    if se2 == 0.0 {
+        return Some(if a.mean == b.mean { 1.0 } else { 0.0 });
+    }
+    let t = (b.mean - a.mean) / se2.sqrt();
+    let df = se2.powi(2) / (va.powi(2) / (a.runs - 1) as f64 + vb.powi(2) / (b.runs - 1) as f64);
+    Some(incomplete_beta(df / 2.0, 0.5, df / (df + t * t)))
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`
+///
+/// See Numerical Recipes, 2nd ed., 6.4
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..300 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        for aa in [
+            m * (b - m) * x / ((a + m2 - 1.0) * (a + m2)),
+            -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0)),
+        ] {
+            d = 1.0 + aa * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + aa / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    h
+}
+
+/// Lanczos approximation, see Numerical Recipes, 2nd ed., 6.1
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut ser = 1.000000000190015;
+    for (i, c) in COEFFS.iter().enumerate() {
+        ser += c / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.5066282746310005 * ser / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(runs: usize, mean: f64, stddev: f64) -> Summary {
+        Summary { runs, mean, stddev }
+    }
+
+    #[test]
+    fn ln_gamma_matches_factorials() {
+        assert!((ln_gamma(5.0) - 24f64.ln()).abs() < 1e-9);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn identical_means_are_not_significant() {
+        let p = welch_t_test(summary(10, 1.0, 0.1), summary(10, 1.0, 0.1)).unwrap();
+        assert!((p - 1.0).abs() < 1e-9, "{p}");
+    }
+
+    #[test]
+    fn matches_reference_p_value() {
+        // scipy.stats.ttest_ind_from_stats(1.0, 0.1, 10, 1.1, 0.1, 10, equal_var=False)
+        let p = welch_t_test(summary(10, 1.0, 0.1), summary(10, 1.1, 0.1)).unwrap();
+        assert!((p - 0.0382).abs() < 1e-3, "{p}");
+    }
+
+    #[test]
+    fn single_run_is_inconclusive() {
+        assert_eq!(
+            welch_t_test(summary(1, 1.0, 0.0), summary(10, 2.0, 0.1)),
+            None
+        );
+    }
+}